@@ -0,0 +1,148 @@
+#![deny(warnings)]
+#![forbid(unsafe_code, clippy::pedantic)]
+
+//! Derive macro for the `Sha2Hash` trait of `compile_time_sha2`, re-exported
+//! by it with the `derive` feature.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, GenericParam, Index};
+
+/// Implements `Sha2Hash` by hashing the fields in declaration order.
+///
+/// Enums are additionally prefixed with the index of the variant, encoded as a
+/// `u32`. Field names are not hashed, thus renaming a field keeps the digest,
+/// while reordering the fields or the variants changes it.
+#[proc_macro_derive(Sha2Hash)]
+pub fn derive_sha2_hash(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(mut input: DeriveInput) -> Result<TokenStream2, Error> {
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let (pattern, bindings) = destructure(&data.fields);
+
+            let hash_fields = hash_bindings(&bindings);
+
+            quote! {
+                let Self #pattern = self;
+
+                #hash_fields
+            }
+        }
+        Data::Enum(data) => {
+            let arms = data
+                .variants
+                .iter()
+                .enumerate()
+                .map(|(index, variant)| {
+                    let index = u32::try_from(index).map_err(|_| {
+                        Error::new_spanned(variant, "too many variants to be indexed by a `u32`")
+                    })?;
+
+                    let name = &variant.ident;
+
+                    let (pattern, bindings) = destructure(&variant.fields);
+
+                    let hash_fields = hash_bindings(&bindings);
+
+                    Ok(quote! {
+                        Self::#name #pattern => {
+                            let __sha = ::compile_time_sha2::structured::Sha2Hash::sha2_hash(&#index, __sha)?;
+
+                            #hash_fields
+                        }
+                    })
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            if arms.is_empty() {
+                quote! {
+                    match *self {}
+                }
+            } else {
+                quote! {
+                    match self {
+                        #(#arms)*
+                    }
+                }
+            }
+        }
+        Data::Union(data) => {
+            return Err(Error::new_spanned(
+                data.union_token,
+                "`Sha2Hash` cannot be derived for unions",
+            ));
+        }
+    };
+
+    for parameter in &mut input.generics.params {
+        if let GenericParam::Type(parameter) = parameter {
+            parameter
+                .bounds
+                .push(parse_quote!(::compile_time_sha2::structured::Sha2Hash));
+        }
+    }
+
+    let name = &input.ident;
+
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::compile_time_sha2::structured::Sha2Hash for #name #type_generics #where_clause {
+            fn sha2_hash<__Sha>(&self, __sha: __Sha) -> ::core::result::Result<__Sha, ::compile_time_sha2::error::MessageTooLong>
+            where
+                __Sha: ::compile_time_sha2::Sha2Algorithm,
+            {
+                #body
+            }
+        }
+    })
+}
+
+/// Returns the pattern binding every field, along with the bindings in
+/// declaration order.
+fn destructure(fields: &Fields) -> (TokenStream2, Vec<TokenStream2>) {
+    match fields {
+        Fields::Named(fields) => {
+            let names = fields
+                .named
+                .iter()
+                .map(|field| field.ident.clone())
+                .collect::<Vec<_>>();
+
+            (
+                quote! { { #(#names),* } },
+                names.iter().map(|name| quote! { #name }).collect(),
+            )
+        }
+        Fields::Unnamed(fields) => {
+            let (indices, names): (Vec<_>, Vec<_>) = (0..fields.unnamed.len())
+                .map(|index| (Index::from(index), format_ident!("field_{index}")))
+                .unzip();
+
+            (
+                quote! { { #(#indices: #names),* } },
+                names.iter().map(|name| quote! { #name }).collect(),
+            )
+        }
+        Fields::Unit => (quote! {}, Vec::new()),
+    }
+}
+
+fn hash_bindings(bindings: &[TokenStream2]) -> TokenStream2 {
+    quote! {
+        #(
+            let __sha = ::compile_time_sha2::structured::Sha2Hash::sha2_hash(#bindings, __sha)?;
+        )*
+
+        ::core::result::Result::Ok(__sha)
+    }
+}
@@ -0,0 +1,178 @@
+use core::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    str::FromStr,
+};
+
+use crate::{
+    consts::{
+        SHA2_224_OUTPUT_LENGTH, SHA2_256_OUTPUT_LENGTH, SHA2_384_OUTPUT_LENGTH,
+        SHA2_512_OUTPUT_LENGTH,
+    },
+    error::ParseError,
+};
+
+/// Identifies a hashing function from the SHA2 family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Algorithm {
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl Algorithm {
+    /// All algorithms implemented by the crate, in ascending order of output size.
+    pub const ALL: [Self; 4] = [Self::Sha224, Self::Sha256, Self::Sha384, Self::Sha512];
+
+    /// Returns the name of the algorithm as spelled in FIPS 180-4, e.g. `SHA-256`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Sha224 => "SHA-224",
+            Self::Sha256 => "SHA-256",
+            Self::Sha384 => "SHA-384",
+            Self::Sha512 => "SHA-512",
+        }
+    }
+
+    /// Returns the name of the algorithm as registered in the IANA "Hash
+    /// Function Textual Names" registry, e.g. `sha-256`.
+    #[must_use]
+    pub const fn iana_name(self) -> &'static str {
+        match self {
+            Self::Sha224 => "sha-224",
+            Self::Sha256 => "sha-256",
+            Self::Sha384 => "sha-384",
+            Self::Sha512 => "sha-512",
+        }
+    }
+
+    /// Returns the name of the algorithm as spelled in BSD-style checksum
+    /// files, e.g. `SHA256`.
+    #[must_use]
+    pub const fn tag_name(self) -> &'static str {
+        match self {
+            Self::Sha224 => "SHA224",
+            Self::Sha256 => "SHA256",
+            Self::Sha384 => "SHA384",
+            Self::Sha512 => "SHA512",
+        }
+    }
+
+    /// Returns the size of the blocks processed by the compression function in
+    /// bytes.
+    #[must_use]
+    pub const fn block_size(self) -> usize {
+        match self {
+            Self::Sha224 | Self::Sha256 => 64,
+            Self::Sha384 | Self::Sha512 => 128,
+        }
+    }
+
+    /// Returns the object identifier assigned by NIST in dotted notation, e.g.
+    /// `2.16.840.1.101.3.4.2.1`.
+    #[must_use]
+    pub const fn oid(self) -> &'static str {
+        match self {
+            Self::Sha224 => "2.16.840.1.101.3.4.2.4",
+            Self::Sha256 => "2.16.840.1.101.3.4.2.1",
+            Self::Sha384 => "2.16.840.1.101.3.4.2.2",
+            Self::Sha512 => "2.16.840.1.101.3.4.2.3",
+        }
+    }
+
+    /// Returns the DER encoding of the contents of the object identifier,
+    /// without the tag and the length.
+    #[must_use]
+    pub const fn oid_der(self) -> &'static [u8] {
+        match self {
+            Self::Sha224 => &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x04],
+            Self::Sha256 => &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01],
+            Self::Sha384 => &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x02],
+            Self::Sha512 => &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03],
+        }
+    }
+
+    /// Looks up an algorithm by name, ignoring case.
+    ///
+    /// Accepts the FIPS 180-4 and IANA spellings, e.g. `SHA-256` and `sha-256`,
+    /// the spelling without separator, e.g. `SHA256`, and the one naming the
+    /// family, e.g. `SHA2-256`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the name does not denote an
+    /// algorithm implemented by the crate.
+    pub fn from_name(name: &str) -> Result<Self, ParseError> {
+        let rest = match name.get(..3) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("sha") => &name[3..],
+            _ => return Err(ParseError::UnknownAlgorithm),
+        };
+
+        let size = rest
+            .strip_prefix("2-")
+            .or_else(|| rest.strip_prefix('-'))
+            .unwrap_or(rest);
+
+        match size {
+            "224" => Ok(Self::Sha224),
+            "256" => Ok(Self::Sha256),
+            "384" => Ok(Self::Sha384),
+            "512" => Ok(Self::Sha512),
+            _ => Err(ParseError::UnknownAlgorithm),
+        }
+    }
+
+    /// Looks up an algorithm by its object identifier in dotted notation.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the identifier is not assigned to
+    /// an algorithm implemented by the crate.
+    pub fn from_oid(oid: &str) -> Result<Self, ParseError> {
+        Self::ALL
+            .into_iter()
+            .find(|algorithm| algorithm.oid() == oid)
+            .ok_or(ParseError::UnknownAlgorithm)
+    }
+
+    /// Looks up an algorithm by the DER encoding of the contents of its object
+    /// identifier, as returned by [`Self::oid_der`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the identifier is not assigned to
+    /// an algorithm implemented by the crate.
+    pub fn from_oid_der(oid_der: &[u8]) -> Result<Self, ParseError> {
+        Self::ALL
+            .into_iter()
+            .find(|algorithm| algorithm.oid_der() == oid_der)
+            .ok_or(ParseError::UnknownAlgorithm)
+    }
+
+    /// Returns the size of the digest in bytes.
+    #[must_use]
+    pub const fn output_length(self) -> usize {
+        match self {
+            Self::Sha224 => SHA2_224_OUTPUT_LENGTH,
+            Self::Sha256 => SHA2_256_OUTPUT_LENGTH,
+            Self::Sha384 => SHA2_384_OUTPUT_LENGTH,
+            Self::Sha512 => SHA2_512_OUTPUT_LENGTH,
+        }
+    }
+}
+
+impl Display for Algorithm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = ParseError;
+
+    /// Equivalent to [`Algorithm::from_name`].
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::from_name(name)
+    }
+}
@@ -0,0 +1,276 @@
+//! Hashing with an algorithm selected at runtime, without allocation.
+//!
+//! ```
+//! use compile_time_sha2::AnySha2;
+//!
+//! let sha = AnySha2::from_name("sha-384").unwrap();
+//!
+//! let digest = sha.update(b"abc").unwrap().finalize();
+//!
+//! assert_eq!(digest.as_bytes().len(), 48);
+//! assert_eq!(
+//!     digest.to_string(),
+//!     "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed\
+//!      8086072ba1e7cc2358baeca134c825a7",
+//! );
+//! ```
+
+use core::fmt::{Display, Formatter, LowerHex, Result as FmtResult, UpperHex};
+
+use crate::{
+    algorithm::Algorithm,
+    consts::{
+        SHA2_224_OUTPUT_LENGTH, SHA2_256_OUTPUT_LENGTH, SHA2_384_OUTPUT_LENGTH,
+        SHA2_512_OUTPUT_LENGTH,
+    },
+    encoding::Hex,
+    error::{MessageTooLong, ParseError},
+    state::ExportedState,
+    Sha224, Sha256, Sha384, Sha512,
+};
+
+/// Hasher of any of the algorithms implemented by the crate.
+#[must_use]
+#[derive(Debug, Clone, Copy)]
+pub enum AnySha2 {
+    Sha224(Sha224),
+    Sha256(Sha256),
+    Sha384(Sha384),
+    Sha512(Sha512),
+}
+
+/// Digest produced by [`AnySha2`], tagged with its algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AnyDigest {
+    Sha224([u8; SHA2_224_OUTPUT_LENGTH]),
+    Sha256([u8; SHA2_256_OUTPUT_LENGTH]),
+    Sha384([u8; SHA2_384_OUTPUT_LENGTH]),
+    Sha512([u8; SHA2_512_OUTPUT_LENGTH]),
+}
+
+impl AnySha2 {
+    pub const fn new(algorithm: Algorithm) -> Self {
+        match algorithm {
+            Algorithm::Sha224 => Self::Sha224(Sha224::new()),
+            Algorithm::Sha256 => Self::Sha256(Sha256::new()),
+            Algorithm::Sha384 => Self::Sha384(Sha384::new()),
+            Algorithm::Sha512 => Self::Sha512(Sha512::new()),
+        }
+    }
+
+    /// Creates a hasher for the algorithm with the given name, as accepted by
+    /// [`Algorithm::from_name`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the name does not denote an
+    /// algorithm implemented by the crate.
+    pub fn from_name(name: &str) -> Result<Self, ParseError> {
+        Algorithm::from_name(name).map(Self::new)
+    }
+
+    /// Creates a hasher for the algorithm with the given object identifier in
+    /// dotted notation.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the identifier is not assigned to
+    /// an algorithm implemented by the crate.
+    pub fn from_oid(oid: &str) -> Result<Self, ParseError> {
+        Algorithm::from_oid(oid).map(Self::new)
+    }
+
+    /// Creates a hasher for the algorithm with the given DER encoded object
+    /// identifier, as accepted by [`Algorithm::from_oid_der`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the identifier is not assigned to
+    /// an algorithm implemented by the crate.
+    pub fn from_oid_der(oid_der: &[u8]) -> Result<Self, ParseError> {
+        Algorithm::from_oid_der(oid_der).map(Self::new)
+    }
+
+    /// Shorthand for creating a hasher, updating it with a single message and
+    /// finalizing it.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the message is too long.
+    pub const fn digest(algorithm: Algorithm, message: &[u8]) -> Result<AnyDigest, MessageTooLong> {
+        match Self::new(algorithm).update(message) {
+            Ok(sha) => Ok(sha.finalize()),
+            Err(error) => Err(error),
+        }
+    }
+
+    #[must_use]
+    pub const fn algorithm(&self) -> Algorithm {
+        match self {
+            Self::Sha224(_) => Algorithm::Sha224,
+            Self::Sha256(_) => Algorithm::Sha256,
+            Self::Sha384(_) => Algorithm::Sha384,
+            Self::Sha512(_) => Algorithm::Sha512,
+        }
+    }
+
+    /// Updates the wrapped hasher with the given message.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the total length of the messages
+    /// exceeds the limit of the algorithm.
+    pub const fn update(self, message: &[u8]) -> Result<Self, MessageTooLong> {
+        macro_rules! update {
+            ($variant: ident, $sha: expr) => {
+                match $sha.update(message) {
+                    Ok(sha) => Ok(Self::$variant(sha)),
+                    Err(error) => Err(error),
+                }
+            };
+        }
+
+        match self {
+            Self::Sha224(sha) => update!(Sha224, sha),
+            Self::Sha256(sha) => update!(Sha256, sha),
+            Self::Sha384(sha) => update!(Sha384, sha),
+            Self::Sha512(sha) => update!(Sha512, sha),
+        }
+    }
+
+    #[must_use]
+    pub const fn finalize(self) -> AnyDigest {
+        match self {
+            Self::Sha224(sha) => AnyDigest::Sha224(sha.finalize()),
+            Self::Sha256(sha) => AnyDigest::Sha256(sha.finalize()),
+            Self::Sha384(sha) => AnyDigest::Sha384(sha.finalize()),
+            Self::Sha512(sha) => AnyDigest::Sha512(sha.finalize()),
+        }
+    }
+
+    /// Writes the first `output.len()` bytes of the digest into `output`, as
+    /// done by the `finalize_into` method of the wrapped hasher.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `output` is longer than the digest.
+    pub fn finalize_into(self, output: &mut [u8]) {
+        match self {
+            Self::Sha224(sha) => sha.finalize_into(output),
+            Self::Sha256(sha) => sha.finalize_into(output),
+            Self::Sha384(sha) => sha.finalize_into(output),
+            Self::Sha512(sha) => sha.finalize_into(output),
+        }
+    }
+
+    /// Returns the digest of the message so far, leaving the hasher untouched so
+    /// that it can be updated further.
+    #[must_use]
+    pub const fn peek_finalize(&self) -> AnyDigest {
+        let snapshot = *self;
+
+        snapshot.finalize()
+    }
+
+    /// Exports the running state of the wrapped hasher.
+    pub const fn export_state(&self) -> ExportedState {
+        match self {
+            Self::Sha224(sha) => sha.export_state(),
+            Self::Sha256(sha) => sha.export_state(),
+            Self::Sha384(sha) => sha.export_state(),
+            Self::Sha512(sha) => sha.export_state(),
+        }
+    }
+
+    /// Resumes hashing from an exported state with the algorithm it was
+    /// exported by.
+    pub const fn import_state(exported: &ExportedState) -> Self {
+        macro_rules! import {
+            ($variant: ident) => {
+                match $variant::import_state(exported) {
+                    Ok(sha) => Self::$variant(sha),
+                    Err(_) => unreachable!(),
+                }
+            };
+        }
+
+        match exported.algorithm() {
+            Algorithm::Sha224 => import!(Sha224),
+            Algorithm::Sha256 => import!(Sha256),
+            Algorithm::Sha384 => import!(Sha384),
+            Algorithm::Sha512 => import!(Sha512),
+        }
+    }
+}
+
+impl From<Sha224> for AnySha2 {
+    fn from(sha: Sha224) -> Self {
+        Self::Sha224(sha)
+    }
+}
+
+impl From<Sha256> for AnySha2 {
+    fn from(sha: Sha256) -> Self {
+        Self::Sha256(sha)
+    }
+}
+
+impl From<Sha384> for AnySha2 {
+    fn from(sha: Sha384) -> Self {
+        Self::Sha384(sha)
+    }
+}
+
+impl From<Sha512> for AnySha2 {
+    fn from(sha: Sha512) -> Self {
+        Self::Sha512(sha)
+    }
+}
+
+impl AnyDigest {
+    #[must_use]
+    pub const fn algorithm(&self) -> Algorithm {
+        match self {
+            Self::Sha224(_) => Algorithm::Sha224,
+            Self::Sha256(_) => Algorithm::Sha256,
+            Self::Sha384(_) => Algorithm::Sha384,
+            Self::Sha512(_) => Algorithm::Sha512,
+        }
+    }
+
+    /// Returns the digest, whose length is the output length of its
+    /// algorithm.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8] {
+        match self {
+            Self::Sha224(digest) => digest,
+            Self::Sha256(digest) => digest,
+            Self::Sha384(digest) => digest,
+            Self::Sha512(digest) => digest,
+        }
+    }
+}
+
+impl AsRef<[u8]> for AnyDigest {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl Display for AnyDigest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        Display::fmt(&Hex(self.as_bytes()), f)
+    }
+}
+
+impl LowerHex for AnyDigest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        LowerHex::fmt(&Hex(self.as_bytes()), f)
+    }
+}
+
+impl UpperHex for AnyDigest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        UpperHex::fmt(&Hex(self.as_bytes()), f)
+    }
+}
@@ -0,0 +1,561 @@
+#![deny(warnings)]
+#![forbid(unsafe_code, clippy::pedantic)]
+
+//! Prints or checks SHA2 checksums, compatible with the `sha*sum` utilities
+//! from GNU coreutils.
+
+use std::{
+    env,
+    ffi::{OsStr, OsString},
+    fs::File,
+    io::{self, BufRead, BufReader, ErrorKind, Read, Write},
+    path::Path,
+    process::ExitCode,
+};
+
+use compile_time_sha2::{
+    algorithm::Algorithm,
+    checksums::{escape_file_name, ChecksumEntry, EntryFormat},
+};
+
+const PROGRAM: &str = "ctsha2sum";
+
+const USAGE: &str = "\
+Usage: ctsha2sum [OPTION]... [FILE]...
+Print or check SHA2 checksums.
+
+With no FILE, or when FILE is -, read standard input.
+
+  -a, --algorithm=BITS  use SHA-224, SHA-256, SHA-384 or SHA-512; the default
+                        is taken from the program name (e.g. sha512sum),
+                        falling back to SHA-256; when checking, BSD-style
+                        lines tagged with another algorithm are improperly
+                        formatted, unless the fallback is used
+  -b, --binary          read in binary mode
+  -c, --check           read checksums from the FILEs and check them
+      --tag             create a BSD-style checksum
+  -t, --text            read in text mode (default)
+
+The following five options are useful only when verifying checksums:
+      --ignore-missing  don't fail or report status for missing files
+      --quiet           don't print OK for each successfully verified file
+      --status          don't output anything, status code shows success
+      --strict          exit non-zero for improperly formatted checksum lines
+  -w, --warn            warn about improperly formatted checksum lines
+
+      --help            display this help and exit
+      --version         output version information and exit
+";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Text,
+    Binary,
+    Tag,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Verbosity {
+    Normal,
+    Quiet,
+    Status,
+}
+
+struct CheckOptions {
+    verbosity: Verbosity,
+    ignore_missing: bool,
+    strict: bool,
+    warn: bool,
+}
+
+struct Options {
+    algorithm: Algorithm,
+    /// Whether the algorithm was selected by an option or the program name,
+    /// rather than being the fallback.
+    algorithm_selected: bool,
+    check: bool,
+    format: OutputFormat,
+    verification: CheckOptions,
+    files: Vec<OsString>,
+}
+
+enum Command {
+    Run(Options),
+    Help,
+    Version,
+}
+
+fn main() -> ExitCode {
+    let options = match parse_arguments(env::args_os()) {
+        Ok(Command::Run(options)) => options,
+        Ok(Command::Help) => {
+            print!("{USAGE}");
+
+            return ExitCode::SUCCESS;
+        }
+        Ok(Command::Version) => {
+            println!("{PROGRAM} {}", env!("CARGO_PKG_VERSION"));
+
+            return ExitCode::SUCCESS;
+        }
+        Err(message) => {
+            eprintln!("{PROGRAM}: {message}\nTry '{PROGRAM} --help' for more information.");
+
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let succeeded = if options.check {
+        check(&options)
+    } else {
+        generate(&options)
+    };
+
+    if succeeded {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn parse_arguments<I>(mut arguments: I) -> Result<Command, String>
+where
+    I: Iterator<Item = OsString>,
+{
+    let mut algorithm = arguments
+        .next()
+        .and_then(|program| algorithm_from_program_name(&program));
+
+    let mut format = OutputFormat::Text;
+
+    let mut tag = false;
+
+    let mut check = false;
+
+    let mut verification = CheckOptions {
+        verbosity: Verbosity::Normal,
+        ignore_missing: false,
+        strict: false,
+        warn: false,
+    };
+
+    let mut check_only_option = false;
+
+    let mut files = Vec::new();
+
+    let mut only_files = false;
+
+    while let Some(argument) = arguments.next() {
+        let text = argument.to_str().unwrap_or_default();
+
+        if only_files || text == "-" || !text.starts_with('-') {
+            files.push(argument);
+        } else if text == "--" {
+            only_files = true;
+        } else if let Some(long) = text.strip_prefix("--") {
+            let (name, value) = match long.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_owned())),
+                None => (long, None),
+            };
+
+            match name {
+                "algorithm" => {
+                    let value = match value {
+                        Some(value) => value,
+                        None => next_value(&mut arguments, "--algorithm")?,
+                    };
+
+                    algorithm = Some(parse_algorithm(&value)?);
+
+                    continue;
+                }
+                "binary" => format = OutputFormat::Binary,
+                "check" => check = true,
+                "tag" => tag = true,
+                "text" => format = OutputFormat::Text,
+                "ignore-missing" => verification.ignore_missing = true,
+                "quiet" => {
+                    if verification.verbosity == Verbosity::Normal {
+                        verification.verbosity = Verbosity::Quiet;
+                    }
+                }
+                "status" => verification.verbosity = Verbosity::Status,
+                "strict" => verification.strict = true,
+                "warn" => verification.warn = true,
+                "help" => return Ok(Command::Help),
+                "version" => return Ok(Command::Version),
+                _ => return Err(format!("unrecognized option '--{name}'")),
+            }
+
+            check_only_option |= matches!(
+                name,
+                "ignore-missing" | "quiet" | "status" | "strict" | "warn"
+            );
+
+            if value.is_some() {
+                return Err(format!("option '--{name}' doesn't allow an argument"));
+            }
+        } else {
+            let flags = &text[1..];
+
+            for (index, flag) in flags.char_indices() {
+                match flag {
+                    'a' => {
+                        let value = &flags[index + 1..];
+
+                        let value = if value.is_empty() {
+                            next_value(&mut arguments, "-a")?
+                        } else {
+                            value.to_owned()
+                        };
+
+                        algorithm = Some(parse_algorithm(&value)?);
+
+                        break;
+                    }
+                    'b' => format = OutputFormat::Binary,
+                    'c' => check = true,
+                    't' => format = OutputFormat::Text,
+                    'w' => {
+                        verification.warn = true;
+
+                        check_only_option = true;
+                    }
+                    _ => return Err(format!("invalid option -- '{flag}'")),
+                }
+            }
+        }
+    }
+
+    reject_conflicting_options(check, tag, check_only_option)?;
+
+    if tag {
+        format = OutputFormat::Tag;
+    }
+
+    if files.is_empty() {
+        files.push(OsString::from("-"));
+    }
+
+    Ok(Command::Run(Options {
+        algorithm: algorithm.unwrap_or(Algorithm::Sha256),
+        algorithm_selected: algorithm.is_some(),
+        check,
+        format,
+        verification,
+        files,
+    }))
+}
+
+fn reject_conflicting_options(
+    check: bool,
+    tag: bool,
+    check_only_option: bool,
+) -> Result<(), String> {
+    if !check && check_only_option {
+        return Err(String::from(
+            "the --ignore-missing, --quiet, --status, --strict and --warn options are meaningful only when verifying checksums",
+        ));
+    }
+
+    if check && tag {
+        return Err(String::from(
+            "the --tag option is meaningless when verifying checksums",
+        ));
+    }
+
+    Ok(())
+}
+
+fn next_value<I>(arguments: &mut I, option: &str) -> Result<String, String>
+where
+    I: Iterator<Item = OsString>,
+{
+    arguments
+        .next()
+        .and_then(|value| value.into_string().ok())
+        .ok_or_else(|| format!("option '{option}' requires an argument"))
+}
+
+fn parse_algorithm(value: &str) -> Result<Algorithm, String> {
+    // Sizes are also accepted on their own, e.g. `--algorithm 256`.
+    Algorithm::from_name(value)
+        .or_else(|_| Algorithm::from_name(&format!("sha{value}")))
+        .map_err(|_| format!("invalid algorithm '{value}'"))
+}
+
+fn algorithm_from_program_name(program: &OsStr) -> Option<Algorithm> {
+    let name = Path::new(program).file_stem()?.to_str()?;
+
+    parse_algorithm(name.strip_suffix("sum")?).ok()
+}
+
+fn open(file_name: &OsStr) -> io::Result<Box<dyn Read>> {
+    if file_name == "-" {
+        Ok(Box::new(io::stdin().lock()))
+    } else {
+        File::open(file_name).map(|file| Box::new(file) as Box<dyn Read>)
+    }
+}
+
+fn describe_error(error: &io::Error) -> String {
+    match error.kind() {
+        ErrorKind::NotFound => String::from("No such file or directory"),
+        ErrorKind::PermissionDenied => String::from("Permission denied"),
+        _ => error.to_string(),
+    }
+}
+
+fn generate(options: &Options) -> bool {
+    let format = match options.format {
+        OutputFormat::Text => EntryFormat::Text,
+        OutputFormat::Binary => EntryFormat::Binary,
+        OutputFormat::Tag => EntryFormat::Tagged,
+    };
+
+    let mut succeeded = true;
+
+    let mut stdout = io::stdout().lock();
+
+    for file_name in &options.files {
+        let display_name = file_name.to_string_lossy();
+
+        let entry = match open(file_name).and_then(|reader| {
+            ChecksumEntry::from_reader(options.algorithm, display_name.clone(), format, reader)
+        }) {
+            Ok(entry) => entry,
+            Err(error) => {
+                _ = stdout.flush();
+
+                eprintln!("{PROGRAM}: {display_name}: {}", describe_error(&error));
+
+                succeeded = false;
+
+                continue;
+            }
+        };
+
+        if writeln!(stdout, "{entry}").is_err() {
+            return false;
+        }
+    }
+
+    succeeded
+}
+
+#[derive(Default)]
+struct CheckCounters {
+    improperly_formatted: usize,
+    mismatched: usize,
+    unreadable: usize,
+    verified: usize,
+    properly_formatted: usize,
+}
+
+fn check(options: &Options) -> bool {
+    let verification = &options.verification;
+
+    let mut succeeded = true;
+
+    for checksum_file in &options.files {
+        let display_name = checksum_file.to_string_lossy();
+
+        let reader = match open(checksum_file) {
+            Ok(reader) => BufReader::new(reader),
+            Err(error) => {
+                eprintln!("{PROGRAM}: {display_name}: {}", describe_error(&error));
+
+                succeeded = false;
+
+                continue;
+            }
+        };
+
+        let mut counters = CheckCounters::default();
+
+        if let Err(error) = check_lines(
+            options.algorithm,
+            options.algorithm_selected,
+            verification,
+            &display_name,
+            reader,
+            &mut counters,
+        ) {
+            eprintln!("{PROGRAM}: {display_name}: {}", describe_error(&error));
+
+            succeeded = false;
+
+            continue;
+        }
+
+        succeeded &= report(options.algorithm, verification, &display_name, &counters);
+    }
+
+    succeeded
+}
+
+fn check_lines(
+    algorithm: Algorithm,
+    algorithm_selected: bool,
+    options: &CheckOptions,
+    checksum_file: &str,
+    mut reader: impl BufRead,
+    counters: &mut CheckCounters,
+) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+
+    let mut line = Vec::new();
+
+    let mut line_number = 0;
+
+    loop {
+        line.clear();
+
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            break Ok(());
+        }
+
+        line_number += 1;
+
+        if line.last() == Some(&b'\n') {
+            line.pop();
+        }
+
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+
+        if line.first() == Some(&b'#') {
+            continue;
+        }
+
+        // Like coreutils, sha512sum rejects lines tagged with another
+        // algorithm, while cksum accepts every algorithm.
+        let Some(entry) = std::str::from_utf8(&line)
+            .ok()
+            .and_then(|line| ChecksumEntry::parse(line, algorithm))
+            .filter(|entry| !algorithm_selected || entry.algorithm == algorithm)
+        else {
+            counters.improperly_formatted += 1;
+
+            if options.warn {
+                _ = stdout.flush();
+
+                eprintln!(
+                    "{PROGRAM}: {checksum_file}: {line_number}: improperly formatted {tag} checksum line",
+                    tag = algorithm.tag_name(),
+                );
+            }
+
+            continue;
+        };
+
+        counters.properly_formatted += 1;
+
+        // Like coreutils, results are only escaped when the name spans lines.
+        let escaped = if entry.file_name.contains(['\n', '\r']) {
+            escape_file_name(&entry.file_name)
+        } else {
+            None
+        };
+
+        let (prefix, display_name) = match &escaped {
+            Some(escaped) => ("\\", escaped.as_str()),
+            None => ("", entry.file_name.as_str()),
+        };
+
+        match open(OsStr::new(&entry.file_name)).and_then(|reader| entry.verify(reader)) {
+            Ok(true) => {
+                counters.verified += 1;
+
+                if options.verbosity == Verbosity::Normal {
+                    writeln!(stdout, "{prefix}{display_name}: OK")?;
+                }
+            }
+            Ok(false) => {
+                counters.verified += 1;
+
+                counters.mismatched += 1;
+
+                if options.verbosity != Verbosity::Status {
+                    writeln!(stdout, "{prefix}{display_name}: FAILED")?;
+                }
+            }
+            Err(error) if options.ignore_missing && error.kind() == ErrorKind::NotFound => {}
+            Err(error) => {
+                counters.unreadable += 1;
+
+                if options.verbosity != Verbosity::Status {
+                    stdout.flush()?;
+
+                    eprintln!("{PROGRAM}: {}: {}", entry.file_name, describe_error(&error));
+
+                    writeln!(stdout, "{prefix}{display_name}: FAILED open or read")?;
+                }
+            }
+        }
+    }
+}
+
+fn report(
+    algorithm: Algorithm,
+    options: &CheckOptions,
+    checksum_file: &str,
+    counters: &CheckCounters,
+) -> bool {
+    let plural = |count: usize, singular: &'static str, plural: &'static str| {
+        if count == 1 {
+            singular
+        } else {
+            plural
+        }
+    };
+
+    _ = io::stdout().flush();
+
+    if counters.properly_formatted == 0 {
+        if options.verbosity != Verbosity::Status {
+            eprintln!(
+                "{PROGRAM}: {checksum_file}: no properly formatted {tag} checksum lines found",
+                tag = algorithm.tag_name(),
+            );
+        }
+
+        return false;
+    }
+
+    if options.verbosity != Verbosity::Status {
+        if counters.improperly_formatted != 0 {
+            eprintln!(
+                "{PROGRAM}: WARNING: {count} {noun} improperly formatted",
+                count = counters.improperly_formatted,
+                noun = plural(counters.improperly_formatted, "line is", "lines are"),
+            );
+        }
+
+        if counters.unreadable != 0 {
+            eprintln!(
+                "{PROGRAM}: WARNING: {count} listed {noun} could not be read",
+                count = counters.unreadable,
+                noun = plural(counters.unreadable, "file", "files"),
+            );
+        }
+
+        if counters.mismatched != 0 {
+            eprintln!(
+                "{PROGRAM}: WARNING: {count} computed {noun} did NOT match",
+                count = counters.mismatched,
+                noun = plural(counters.mismatched, "checksum", "checksums"),
+            );
+        }
+
+        if options.ignore_missing && counters.verified == 0 && counters.unreadable == 0 {
+            eprintln!("{PROGRAM}: {checksum_file}: no file was verified");
+        }
+    }
+
+    counters.mismatched == 0
+        && counters.unreadable == 0
+        && !(options.strict && counters.improperly_formatted != 0)
+        && !(options.ignore_missing && counters.verified == 0 && counters.unreadable == 0)
+}
@@ -0,0 +1,258 @@
+//! Block headers and their proof of work.
+//!
+//! ```
+//! use compile_time_sha2::bitcoin::header::{BlockHeader, Target};
+//!
+//! let genesis = BlockHeader {
+//!     version: 1,
+//!     previous_block_hash: [0; 32],
+//!     merkle_root: *b"\x3b\xa3\xed\xfd\x7a\x7b\x12\xb2\x7a\xc7\x2c\x3e\x67\x76\x8f\x61\
+//!                     \x7f\xc8\x1b\xc3\x88\x8a\x51\x32\x3a\x9f\xb8\xaa\x4b\x1e\x5e\x4a",
+//!     time: 1_231_006_505,
+//!     bits: 0x1D00_FFFF,
+//!     nonce: 2_083_236_893,
+//! };
+//!
+//! let mut hash = genesis.block_hash();
+//!
+//! hash.reverse();
+//!
+//! assert!(hash.starts_with(&[0x00, 0x00, 0x00, 0x00, 0x00, 0x19, 0xD6, 0x68]));
+//! assert_eq!(genesis.check_proof_of_work(&Target::MAINNET_LIMIT), Ok(()));
+//! ```
+
+use crate::{
+    constant_time::ct_eq,
+    error::{ParseError, ProofOfWorkError},
+    Sha256,
+};
+
+/// Length of a serialized block header.
+pub const HEADER_LENGTH: usize = 80;
+
+/// Header of a block, with hashes in the byte order of the serialization,
+/// i.e. reversed with respect to how Bitcoin displays them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BlockHeader {
+    pub version: i32,
+    pub previous_block_hash: [u8; 32],
+    pub merkle_root: [u8; 32],
+    /// Seconds since the Unix epoch.
+    pub time: u32,
+    /// Target encoded in the compact format, see [`Target::from_compact`].
+    pub bits: u32,
+    pub nonce: u32,
+}
+
+impl BlockHeader {
+    /// Parses a serialized header, whose integers are little-endian.
+    #[must_use]
+    pub const fn parse(bytes: &[u8; HEADER_LENGTH]) -> Self {
+        Self {
+            version: i32::from_le_bytes(read(bytes, 0)),
+            previous_block_hash: read(bytes, 4),
+            merkle_root: read(bytes, 36),
+            time: u32::from_le_bytes(read(bytes, 68)),
+            bits: u32::from_le_bytes(read(bytes, 72)),
+            nonce: u32::from_le_bytes(read(bytes, 76)),
+        }
+    }
+
+    /// Parses a serialized header from a slice.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the slice is not exactly
+    /// [`HEADER_LENGTH`] bytes long.
+    pub const fn from_slice(bytes: &[u8]) -> Result<Self, ParseError> {
+        if bytes.len() == HEADER_LENGTH {
+            Ok(Self::parse(&read(bytes, 0)))
+        } else {
+            Err(ParseError::InvalidLength {
+                expected: HEADER_LENGTH,
+                actual: bytes.len(),
+            })
+        }
+    }
+
+    #[must_use]
+    pub const fn to_bytes(&self) -> [u8; HEADER_LENGTH] {
+        let mut bytes = [0; HEADER_LENGTH];
+
+        bytes = write(bytes, 0, &self.version.to_le_bytes());
+        bytes = write(bytes, 4, &self.previous_block_hash);
+        bytes = write(bytes, 36, &self.merkle_root);
+        bytes = write(bytes, 68, &self.time.to_le_bytes());
+        bytes = write(bytes, 72, &self.bits.to_le_bytes());
+
+        write(bytes, 76, &self.nonce.to_le_bytes())
+    }
+
+    /// Returns the double SHA-256 digest of the serialized header.
+    #[must_use]
+    pub const fn block_hash(&self) -> [u8; 32] {
+        Sha256::digest_array(&Sha256::digest_array(&self.to_bytes()))
+    }
+
+    /// Checks that the target is valid and not above `limit`, and that the
+    /// hash of the header does not exceed it.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error describing the first failed check.
+    pub const fn check_proof_of_work(&self, limit: &Target) -> Result<(), ProofOfWorkError> {
+        let target = match Target::from_compact(self.bits) {
+            Ok(target) => target,
+            Err(error) => return Err(error),
+        };
+
+        if ct_eq(&target.0, &[0; 32]) {
+            Err(ProofOfWorkError::ZeroTarget)
+        } else if compare(&target.0, &limit.0).is_gt() {
+            Err(ProofOfWorkError::TargetAboveLimit)
+        } else if target.is_met_by(&self.block_hash()) {
+            Ok(())
+        } else {
+            Err(ProofOfWorkError::InsufficientWork)
+        }
+    }
+}
+
+/// Proof-of-work target, a 256-bit unsigned integer which block hashes must
+/// not exceed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Target([u8; 32]);
+
+impl Target {
+    /// Highest target of the main network, encoded as `0x1D00FFFF`.
+    pub const MAINNET_LIMIT: Self = {
+        let mut bytes = [0; 32];
+
+        bytes[4] = 0xFF;
+        bytes[5] = 0xFF;
+
+        Self(bytes)
+    };
+
+    #[must_use]
+    pub const fn from_be_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    #[must_use]
+    pub const fn to_be_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+
+    /// Decodes the compact format of the `bits` field: the highest byte is the
+    /// length of the target in bytes, and the lower three bytes are its most
+    /// significant bytes, the highest bit being a sign.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the target is negative or does
+    /// not fit into 256 bits.
+    pub const fn from_compact(bits: u32) -> Result<Self, ProofOfWorkError> {
+        let [size, mantissa @ ..] = bits.to_be_bytes();
+
+        let size = size as usize;
+
+        // As in Bitcoin Core, mantissa bytes shifted out by a small size do
+        // not count towards the sign and the overflow.
+        let word = if size <= 3 {
+            (bits & 0x007F_FFFF) >> (8 * (3 - size))
+        } else {
+            bits & 0x007F_FFFF
+        };
+
+        if word != 0 && bits & 0x0080_0000 != 0 {
+            return Err(ProofOfWorkError::NegativeTarget);
+        }
+
+        if word != 0 && (size > 34 || (word > 0xFF && size > 33) || (word > 0xFFFF && size > 32)) {
+            return Err(ProofOfWorkError::TargetOverflow);
+        }
+
+        let mut bytes = [0; 32];
+
+        let mut index = 0;
+
+        // The mantissa without the sign bit is placed so that its last byte
+        // is the `size`-th byte from the end, bytes past the end being dropped.
+        while index < mantissa.len() {
+            if index < size && size <= 32 + index {
+                bytes[32 + index - size] = if index == 0 {
+                    mantissa[0] & 0x7F
+                } else {
+                    mantissa[index]
+                };
+            }
+
+            index += 1;
+        }
+
+        Ok(Self(bytes))
+    }
+
+    /// Returns whether the block hash, read as a little-endian integer, does
+    /// not exceed the target.
+    #[must_use]
+    pub const fn is_met_by(&self, block_hash: &[u8; 32]) -> bool {
+        let mut reversed = *block_hash;
+
+        let mut index = 0;
+
+        while index < 16 {
+            (reversed[index], reversed[31 - index]) = (reversed[31 - index], reversed[index]);
+
+            index += 1;
+        }
+
+        compare(&reversed, &self.0).is_le()
+    }
+}
+
+/// Compares big-endian integers of the same length.
+const fn compare(left: &[u8; 32], right: &[u8; 32]) -> core::cmp::Ordering {
+    let mut index = 0;
+
+    while index < 32 {
+        if left[index] != right[index] {
+            return if left[index] < right[index] {
+                core::cmp::Ordering::Less
+            } else {
+                core::cmp::Ordering::Greater
+            };
+        }
+
+        index += 1;
+    }
+
+    core::cmp::Ordering::Equal
+}
+
+const fn read<const N: usize>(bytes: &[u8], offset: usize) -> [u8; N] {
+    let mut output = [0; N];
+
+    let mut index = 0;
+
+    while index < N {
+        output[index] = bytes[offset + index];
+
+        index += 1;
+    }
+
+    output
+}
+
+const fn write(mut bytes: [u8; HEADER_LENGTH], offset: usize, value: &[u8]) -> [u8; HEADER_LENGTH] {
+    let mut index = 0;
+
+    while index < value.len() {
+        bytes[offset + index] = value[index];
+
+        index += 1;
+    }
+
+    bytes
+}
@@ -0,0 +1,120 @@
+//! Merkle trees of block transactions.
+//!
+//! Each node is the double SHA-256 digest of its two children. A level with
+//! an odd number of nodes pairs its last node with itself, so that e.g. the
+//! transactions `[a, b, c]` and `[a, b, c, c]` have the same root. Such lists
+//! are rejected by [`merkle_root`], as Bitcoin Core does since CVE-2012-2459.
+//!
+//! ```
+//! use compile_time_sha2::bitcoin::merkle::{merkle_root, verify_merkle_branch};
+//!
+//! let txids = [[1; 32], [2; 32], [3; 32]];
+//!
+//! let root = merkle_root(&txids).unwrap();
+//!
+//! let branch = [[3; 32], merkle_root(&txids[..2]).unwrap()];
+//!
+//! assert!(verify_merkle_branch(&txids[2], &branch, 2, &root));
+//! assert!(merkle_root(&[[1; 32], [2; 32], [3; 32], [3; 32]]).is_err());
+//! ```
+
+use crate::{constant_time::ct_eq, error::MerkleError, Sha256};
+
+/// Returns the Merkle root of the transaction identifiers, in the byte order
+/// of their serialization.
+///
+/// # Errors
+///
+/// This function will return an error if there are no transactions, or if
+/// two sibling nodes of the tree are equal.
+pub const fn merkle_root(txids: &[[u8; 32]]) -> Result<[u8; 32], MerkleError> {
+    if txids.is_empty() {
+        return Err(MerkleError::NoTransactions);
+    }
+
+    let mut height = 0;
+
+    while (txids.len() - 1) >> height != 0 {
+        height += 1;
+    }
+
+    match node(txids, height, 0) {
+        (_, true) => Err(MerkleError::Mutated),
+        (root, false) => Ok(root),
+    }
+}
+
+/// Returns the node at `index` of the level `height` above the leaves, and
+/// whether its subtree has equal siblings.
+const fn node(txids: &[[u8; 32]], height: u32, index: usize) -> ([u8; 32], bool) {
+    if height == 0 {
+        return (txids[index], false);
+    }
+
+    let (left, left_mutated) = node(txids, height - 1, 2 * index);
+
+    // A node exists if the first leaf below it does.
+    let (right, mutated) = if (2 * index + 1) << (height - 1) < txids.len() {
+        let (right, right_mutated) = node(txids, height - 1, 2 * index + 1);
+
+        (right, left_mutated || right_mutated || ct_eq(&left, &right))
+    } else {
+        (left, left_mutated)
+    };
+
+    (parent(&left, &right), mutated)
+}
+
+const fn parent(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut children = [0; 64];
+
+    let mut index = 0;
+
+    while index < 32 {
+        children[index] = left[index];
+        children[32 + index] = right[index];
+
+        index += 1;
+    }
+
+    Sha256::digest_array(&Sha256::digest_array(&children))
+}
+
+/// Returns the root reached from a transaction by hashing it with the
+/// siblings along its path, from the leaves up. Bit `i` of `index`, the
+/// position of the transaction in the block, tells whether the sibling at
+/// level `i` is on the left.
+#[must_use]
+pub const fn merkle_root_from_branch(
+    txid: &[u8; 32],
+    branch: &[[u8; 32]],
+    index: usize,
+) -> [u8; 32] {
+    let mut node = *txid;
+
+    let mut level = 0;
+
+    while level < branch.len() {
+        node = if level < usize::BITS as usize && (index >> level) & 1 == 1 {
+            parent(&branch[level], &node)
+        } else {
+            parent(&node, &branch[level])
+        };
+
+        level += 1;
+    }
+
+    node
+}
+
+/// Returns whether the branch proves that the transaction is at `index` in
+/// the tree of the given root.
+#[must_use]
+pub const fn verify_merkle_branch(
+    txid: &[u8; 32],
+    branch: &[[u8; 32]],
+    index: usize,
+    root: &[u8; 32],
+) -> bool {
+    ct_eq(&merkle_root_from_branch(txid, branch, index), root)
+}
@@ -0,0 +1,131 @@
+//! Hashing constructions used by Bitcoin.
+//!
+//! Identifiers of blocks and transactions are double SHA-256 digests, computed
+//! by [`sha256d`]. BIP-340 hashes messages with a [`TaggedHash`], prefixing
+//! them with the digest of a tag twice, which fills exactly one block. The
+//! state after that block is computed once, possibly while compiling:
+//!
+//! ```
+//! use compile_time_sha2::{bitcoin::TaggedHash, Sha256};
+//!
+//! const CHALLENGE: TaggedHash = TaggedHash::new(b"BIP0340/challenge");
+//!
+//! let tag = Sha256::digest(b"BIP0340/challenge").unwrap();
+//!
+//! assert_eq!(
+//!     CHALLENGE.hash(b"message").unwrap(),
+//!     Sha256::digest_parts(&[&tag, &tag, b"message"]).unwrap(),
+//! );
+//! ```
+//!
+//! Digests are returned in the order produced by SHA-256. Bitcoin displays
+//! block and transaction identifiers with their bytes reversed.
+//!
+//! Block headers and their proof of work are handled by [`header`], and the
+//! Merkle trees committing to the transactions of a block by [`merkle`].
+
+pub mod header;
+pub mod merkle;
+
+use crate::{error::MessageTooLong, Sha256};
+
+/// Returns `SHA256(SHA256(message))`.
+///
+/// # Errors
+///
+/// This function will return an error if the message exceeds the length limit
+/// of SHA-256.
+pub const fn sha256d(message: &[u8]) -> Result<[u8; 32], MessageTooLong> {
+    match Sha256::digest(message) {
+        Ok(digest) => Ok(Sha256::digest_array(&digest)),
+        Err(error) => Err(error),
+    }
+}
+
+/// Returns the double SHA-256 digest of the concatenation of the parts.
+///
+/// # Errors
+///
+/// This function will return an error if the concatenation exceeds the length
+/// limit of SHA-256.
+pub const fn sha256d_parts(parts: &[&[u8]]) -> Result<[u8; 32], MessageTooLong> {
+    match Sha256::digest_parts(parts) {
+        Ok(digest) => Ok(Sha256::digest_array(&digest)),
+        Err(error) => Err(error),
+    }
+}
+
+/// Tagged hash of BIP-340, `SHA256(SHA256(tag) || SHA256(tag) || message)`.
+///
+/// Holds the state after the 64-byte prefix, which every hash resumes from.
+#[must_use]
+#[derive(Debug, Clone, Copy)]
+pub struct TaggedHash {
+    prefixed: Sha256,
+}
+
+impl TaggedHash {
+    /// Hashes the tag and processes the prefix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tag exceeds the length limit of SHA-256.
+    pub const fn new(tag: &[u8]) -> Self {
+        let Ok(tag) = Sha256::digest(tag) else {
+            panic!("Tag too long to be processed by the SHA-256 algorithm!");
+        };
+
+        let Ok(prefixed) = Sha256::new().update(&tag) else {
+            unreachable!()
+        };
+
+        let Ok(prefixed) = prefixed.update(&tag) else {
+            unreachable!()
+        };
+
+        Self { prefixed }
+    }
+
+    /// Returns a hasher which already processed the prefix, to be updated
+    /// with the message.
+    pub const fn hasher(&self) -> Sha256 {
+        self.prefixed
+    }
+
+    /// Returns the tagged hash of the message.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the prefixed message exceeds the
+    /// length limit of SHA-256.
+    pub const fn hash(&self, message: &[u8]) -> Result<[u8; 32], MessageTooLong> {
+        match self.prefixed.update(message) {
+            Ok(sha) => Ok(sha.finalize()),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Returns the tagged hash of the concatenation of the parts, e.g. of
+    /// the nonce, the public key and the message of a BIP-340 challenge.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the prefixed concatenation
+    /// exceeds the length limit of SHA-256.
+    pub const fn hash_parts(&self, parts: &[&[u8]]) -> Result<[u8; 32], MessageTooLong> {
+        let mut sha = self.prefixed;
+
+        let mut index = 0;
+
+        while index < parts.len() {
+            sha = match sha.update(parts[index]) {
+                Ok(sha) => sha,
+                Err(error) => return Err(error),
+            };
+
+            index += 1;
+        }
+
+        Ok(sha.finalize())
+    }
+}
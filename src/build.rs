@@ -0,0 +1,297 @@
+//! Generation of digest manifests from build scripts.
+//!
+//! A build script walks a directory and writes a Rust module listing every
+//! selected file together with its length and digest:
+//!
+//! ```no_run
+//! use compile_time_sha2::{algorithm::Algorithm, build::ManifestBuilder};
+//!
+//! let out_dir = std::env::var_os("OUT_DIR").unwrap();
+//!
+//! ManifestBuilder::new("assets")
+//!     .algorithm(Algorithm::Sha256)
+//!     .include("**/*.css")
+//!     .include("**/*.js")
+//!     .exclude("vendor/**")
+//!     .write(std::path::Path::new(&out_dir).join("assets.rs"))
+//!     .unwrap();
+//! ```
+//!
+//! The generated source only depends on `core` and is identical for identical
+//! inputs, as entries are sorted by path.
+
+use std::{
+    fmt::Write as _,
+    fs::{self, File},
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+use crate::{algorithm::Algorithm, checksums::hash_reader};
+
+/// File selected for the manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestEntry {
+    /// Path relative to the root directory, with components separated by `/`.
+    pub path: String,
+    pub length: u64,
+    pub digest: Vec<u8>,
+}
+
+/// Collects files under a root directory and generates the manifest module.
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct ManifestBuilder {
+    root: PathBuf,
+    algorithm: Algorithm,
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl ManifestBuilder {
+    /// Creates a builder selecting every file under `root`, hashed with SHA-256.
+    pub fn new<P>(root: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self {
+            root: root.into(),
+            algorithm: Algorithm::Sha256,
+            include: Vec::new(),
+            exclude: Vec::new(),
+        }
+    }
+
+    pub fn algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithm = algorithm;
+
+        self
+    }
+
+    /// Adds a glob pattern selecting files by their relative path.
+    ///
+    /// When no pattern is included, every file is selected. Patterns support
+    /// `?` and `*`, which do not match `/`, and `**`, which matches any number
+    /// of directories.
+    pub fn include<S>(mut self, pattern: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.include.push(pattern.into());
+
+        self
+    }
+
+    /// Adds a glob pattern rejecting files by their relative path, taking
+    /// precedence over the included patterns.
+    pub fn exclude<S>(mut self, pattern: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.exclude.push(pattern.into());
+
+        self
+    }
+
+    /// Walks the root directory and hashes the selected files, sorted by path.
+    ///
+    /// Symbolic links to files are followed, while symbolic links to
+    /// directories are skipped.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a directory or file cannot be
+    /// read, or if a selected path is not valid UTF-8.
+    pub fn entries(&self) -> io::Result<Vec<ManifestEntry>> {
+        let mut entries = Vec::new();
+
+        self.collect(&self.root, "", &mut entries)?;
+
+        entries.sort_unstable_by(|left, right| left.path.cmp(&right.path));
+
+        Ok(entries)
+    }
+
+    /// Generates the source of the manifest module.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error under the same conditions as
+    /// [`Self::entries`].
+    pub fn generate(&self) -> io::Result<String> {
+        Ok(render(self.algorithm, &self.entries()?))
+    }
+
+    /// Generates the manifest module and writes it to the given path, leaving
+    /// the file untouched when its contents are already up to date.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error under the same conditions as
+    /// [`Self::entries`], or if the file cannot be written.
+    pub fn write<P>(&self, path: P) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let source = self.generate()?;
+
+        if matches!(fs::read(path.as_ref()), Ok(existing) if existing == source.as_bytes()) {
+            return Ok(());
+        }
+
+        fs::write(path, source)
+    }
+
+    fn collect(
+        &self,
+        directory: &Path,
+        relative_directory: &str,
+        entries: &mut Vec<ManifestEntry>,
+    ) -> io::Result<()> {
+        for entry in fs::read_dir(directory)? {
+            let entry = entry?;
+
+            let Ok(name) = entry.file_name().into_string() else {
+                return Err(io::Error::new(
+                    ErrorKind::InvalidData,
+                    format!("path under {} is not valid UTF-8", directory.display()),
+                ));
+            };
+
+            let relative_path = if relative_directory.is_empty() {
+                name
+            } else {
+                format!("{relative_directory}/{name}")
+            };
+
+            let path = entry.path();
+
+            if entry.file_type()?.is_dir() {
+                self.collect(&path, &relative_path, entries)?;
+            } else if fs::metadata(&path)?.is_file() && self.is_selected(&relative_path) {
+                let (length, digest) = hash_reader(self.algorithm, File::open(&path)?)?;
+
+                entries.push(ManifestEntry {
+                    path: relative_path,
+                    length,
+                    digest,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn is_selected(&self, relative_path: &str) -> bool {
+        let matches = |pattern: &String| glob_matches(pattern.as_bytes(), relative_path.as_bytes());
+
+        (self.include.is_empty() || self.include.iter().any(matches))
+            && !self.exclude.iter().any(matches)
+    }
+}
+
+fn render(algorithm: Algorithm, entries: &[ManifestEntry]) -> String {
+    let mut source = format!(
+        "// @generated by compile_time_sha2::build. Do not edit.
+
+pub const ALGORITHM: &str = {name:?};
+
+pub struct Entry {{
+    pub path: &'static str,
+    pub length: u64,
+    pub digest: [u8; {output_length}],
+}}
+
+/// Entries sorted by path.
+pub const ENTRIES: &[Entry] = &[
+",
+        name = algorithm.name(),
+        output_length = algorithm.output_length(),
+    );
+
+    for entry in entries {
+        _ = write!(
+            source,
+            "    Entry {{
+        path: {path:?},
+        length: {length},
+        digest: *b\"",
+            path = entry.path,
+            length = entry.length,
+        );
+
+        for byte in &entry.digest {
+            _ = write!(source, "\\x{byte:02x}");
+        }
+
+        _ = write!(source, "\",\n    }},\n");
+    }
+
+    source.push_str(
+        "];
+
+pub fn get(path: &str) -> Option<&'static Entry> {
+    ENTRIES
+        .binary_search_by(|entry| entry.path.cmp(path))
+        .ok()
+        .map(|index| &ENTRIES[index])
+}
+",
+    );
+
+    source
+}
+
+/// Matches a path against a glob pattern, where `?` and `*` do not match `/`
+/// and `**` matches any number of directories, including none.
+fn glob_matches(pattern: &[u8], path: &[u8]) -> bool {
+    match pattern {
+        [] => path.is_empty(),
+        [b'*', b'*', rest @ ..] => {
+            if let [b'/', after_separator @ ..] = rest {
+                if glob_matches(after_separator, path) {
+                    return true;
+                }
+            }
+
+            (0..=path.len()).any(|index| glob_matches(rest, &path[index..]))
+        }
+        [b'*', rest @ ..] => {
+            for index in 0..=path.len() {
+                if glob_matches(rest, &path[index..]) {
+                    return true;
+                }
+
+                if path.get(index) == Some(&b'/') {
+                    break;
+                }
+            }
+
+            false
+        }
+        [b'?', rest @ ..] => {
+            matches!(path, [character, tail @ ..] if *character != b'/' && glob_matches(rest, tail))
+        }
+        [expected, rest @ ..] => {
+            matches!(path, [character, tail @ ..] if character == expected && glob_matches(rest, tail))
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_glob_matches() {
+    let matches = |pattern: &str, path: &str| glob_matches(pattern.as_bytes(), path.as_bytes());
+
+    assert!(matches("*.css", "site.css"));
+    assert!(!matches("*.css", "css/site.css"));
+    assert!(matches("**/*.css", "site.css"));
+    assert!(matches("**/*.css", "a/b/site.css"));
+    assert!(matches("vendor/**", "vendor/a/b.js"));
+    assert!(!matches("vendor/**", "src/vendor.js"));
+    assert!(matches("img/?.png", "img/a.png"));
+    assert!(!matches("img/?.png", "img/ab.png"));
+    assert!(!matches("img?a.png", "img/a.png"));
+    assert!(matches("a/**/b", "a/b"));
+    assert!(matches("a/**/b", "a/x/y/b"));
+}
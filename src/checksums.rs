@@ -0,0 +1,448 @@
+//! Reading, writing and verifying checksum files in the format of the
+//! `sha*sum` utilities from GNU coreutils, e.g. `SHA256SUMS`.
+//!
+//! Both the GNU format, with text and binary markers, and the BSD tagged
+//! format are supported, including escaped file names and comments:
+//!
+//! ```text
+//! # Release 1.0
+//! ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  abc.txt
+//! ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad *abc.bin
+//! SHA256 (abc.txt) = ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad
+//! \ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  a\\b.txt
+//! ```
+
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    fs::File,
+    io::{self, ErrorKind, Read},
+    path::Path,
+};
+
+use crate::{
+    algorithm::Algorithm,
+    encoding::{decode_hex_into, Hex},
+    error::MessageTooLong,
+    AnySha2,
+};
+
+/// Size of the buffer used when reading files.
+const READ_BUFFER_SIZE: usize = 1 << 16;
+
+/// How an entry is written in a checksum file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntryFormat {
+    /// GNU format, e.g. `<digest>  <file name>`.
+    Text,
+    /// GNU format with the binary marker, e.g. `<digest> *<file name>`.
+    Binary,
+    /// BSD format, e.g. `SHA256 (<file name>) = <digest>`.
+    Tagged,
+}
+
+/// Line of a checksum file, associating a file name with its digest.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChecksumEntry {
+    pub algorithm: Algorithm,
+    pub digest: Vec<u8>,
+    /// File name, unescaped.
+    pub file_name: String,
+    pub format: EntryFormat,
+}
+
+impl ChecksumEntry {
+    /// Hashes the data read from the reader, creating an entry for it.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if reading fails.
+    pub fn from_reader<S, R>(
+        algorithm: Algorithm,
+        file_name: S,
+        format: EntryFormat,
+        reader: R,
+    ) -> io::Result<Self>
+    where
+        S: Into<String>,
+        R: Read,
+    {
+        let (_, digest) = hash_reader(algorithm, reader)?;
+
+        Ok(Self {
+            algorithm,
+            digest,
+            file_name: file_name.into(),
+            format,
+        })
+    }
+
+    /// Parses a single line, without its line terminator.
+    ///
+    /// Lines in the GNU format do not name the algorithm, so the default one is
+    /// assumed for them. Tagged lines are parsed with the algorithm they name,
+    /// even when it differs from the default one; callers expecting a single
+    /// algorithm must compare it with [`Self::algorithm`]. Returns [`None`]
+    /// for comments and improperly formatted lines.
+    #[must_use]
+    pub fn parse(line: &str, default_algorithm: Algorithm) -> Option<Self> {
+        if line.starts_with('#') {
+            return None;
+        }
+
+        let (escaped, line) = match line.strip_prefix('\\') {
+            Some(line) => (true, line),
+            None => (false, line),
+        };
+
+        // File names of GNU lines may contain " (", thus only a known tag
+        // before it marks a tagged line.
+        let tagged = line.split_once(" (").and_then(|(tag, rest)| {
+            Algorithm::ALL
+                .into_iter()
+                .find(|algorithm| algorithm.tag_name() == tag)
+                .map(|algorithm| (algorithm, rest))
+        });
+
+        let (algorithm, format, hex_digest, file_name) = if let Some((algorithm, rest)) = tagged {
+            let (file_name, hex_digest) = rest.rsplit_once(") = ")?;
+
+            (algorithm, EntryFormat::Tagged, hex_digest, file_name)
+        } else {
+            let (hex_digest, rest) = line.split_once(' ')?;
+
+            let (format, file_name) = if let Some(file_name) = rest.strip_prefix('*') {
+                (EntryFormat::Binary, file_name)
+            } else {
+                (EntryFormat::Text, rest.strip_prefix(' ').unwrap_or(rest))
+            };
+
+            (default_algorithm, format, hex_digest, file_name)
+        };
+
+        if file_name.is_empty() {
+            return None;
+        }
+
+        let mut digest = vec![0; algorithm.output_length()];
+
+        decode_hex_into(hex_digest, &mut digest).ok()?;
+
+        let file_name = if escaped {
+            unescape_file_name(file_name)?
+        } else {
+            file_name.to_owned()
+        };
+
+        Some(Self {
+            algorithm,
+            digest,
+            file_name,
+            format,
+        })
+    }
+
+    /// Hashes the data read from the reader and compares it with the digest of
+    /// the entry.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if reading fails.
+    pub fn verify<R>(&self, reader: R) -> io::Result<bool>
+    where
+        R: Read,
+    {
+        hash_reader(self.algorithm, reader).map(|(_, digest)| digest == self.digest)
+    }
+}
+
+impl Display for ChecksumEntry {
+    /// Formats the entry as a line, without the line terminator.
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let escaped = escape_file_name(&self.file_name);
+
+        let (prefix, file_name) = match &escaped {
+            Some(escaped) => ("\\", escaped.as_str()),
+            None => ("", self.file_name.as_str()),
+        };
+
+        let digest = Hex(&self.digest);
+
+        match self.format {
+            EntryFormat::Text => write!(f, "{prefix}{digest}  {file_name}"),
+            EntryFormat::Binary => write!(f, "{prefix}{digest} *{file_name}"),
+            EntryFormat::Tagged => write!(
+                f,
+                "{prefix}{tag} ({file_name}) = {digest}",
+                tag = self.algorithm.tag_name(),
+            ),
+        }
+    }
+}
+
+/// Contents of a checksum file.
+///
+/// Formatting it with [`Display`] produces the file, one entry per line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChecksumFile {
+    entries: Vec<ChecksumEntry>,
+    improperly_formatted_lines: Vec<usize>,
+}
+
+impl ChecksumFile {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            improperly_formatted_lines: Vec::new(),
+        }
+    }
+
+    /// Parses a checksum file, skipping comments and recording improperly
+    /// formatted lines instead of failing.
+    ///
+    /// See [`ChecksumEntry::parse`] for the meaning of the default algorithm.
+    #[must_use]
+    pub fn parse(text: &str, default_algorithm: Algorithm) -> Self {
+        let mut file = Self::new();
+
+        for (index, line) in text.lines().enumerate() {
+            if line.starts_with('#') {
+                continue;
+            }
+
+            match ChecksumEntry::parse(line, default_algorithm) {
+                Some(entry) => file.entries.push(entry),
+                None => file.improperly_formatted_lines.push(index + 1),
+            }
+        }
+
+        file
+    }
+
+    #[must_use]
+    pub fn entries(&self) -> &[ChecksumEntry] {
+        &self.entries
+    }
+
+    /// Returns the one-based numbers of the lines which could not be parsed.
+    #[must_use]
+    pub fn improperly_formatted_lines(&self) -> &[usize] {
+        &self.improperly_formatted_lines
+    }
+
+    pub fn push(&mut self, entry: ChecksumEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Verifies every entry against the data returned by `open`.
+    ///
+    /// Errors of kind [`ErrorKind::NotFound`] are reported as
+    /// [`VerificationStatus::Missing`].
+    pub fn verify_with<F, R>(&self, mut open: F) -> VerificationReport<'_>
+    where
+        F: FnMut(&ChecksumEntry) -> io::Result<R>,
+        R: Read,
+    {
+        let entries = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let status = match open(entry).and_then(|reader| entry.verify(reader)) {
+                    Ok(true) => VerificationStatus::Matched,
+                    Ok(false) => VerificationStatus::Mismatched,
+                    Err(error) if error.kind() == ErrorKind::NotFound => {
+                        VerificationStatus::Missing
+                    }
+                    Err(error) => VerificationStatus::Unreadable(error),
+                };
+
+                EntryReport { entry, status }
+            })
+            .collect();
+
+        VerificationReport { entries }
+    }
+
+    /// Verifies every entry against the file it names, relative to the given
+    /// directory.
+    pub fn verify_files<P>(&self, directory: P) -> VerificationReport<'_>
+    where
+        P: AsRef<Path>,
+    {
+        let directory = directory.as_ref();
+
+        self.verify_with(|entry| File::open(directory.join(&entry.file_name)))
+    }
+
+    /// Verifies every entry against in-memory data, looked up by file name.
+    ///
+    /// Entries for which no data is returned are reported as
+    /// [`VerificationStatus::Missing`].
+    pub fn verify_data<'d, F>(&self, mut data: F) -> VerificationReport<'_>
+    where
+        F: FnMut(&str) -> Option<&'d [u8]>,
+    {
+        self.verify_with(|entry| {
+            data(&entry.file_name).ok_or_else(|| io::Error::from(ErrorKind::NotFound))
+        })
+    }
+}
+
+impl Display for ChecksumFile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        self.entries
+            .iter()
+            .try_for_each(|entry| writeln!(f, "{entry}"))
+    }
+}
+
+/// Outcome of verifying a single entry.
+#[derive(Debug)]
+pub enum VerificationStatus {
+    Matched,
+    Mismatched,
+    Missing,
+    Unreadable(io::Error),
+}
+
+/// Outcome of verifying an entry, along with the entry itself.
+#[derive(Debug)]
+pub struct EntryReport<'r> {
+    entry: &'r ChecksumEntry,
+    status: VerificationStatus,
+}
+
+impl<'r> EntryReport<'r> {
+    #[must_use]
+    pub const fn entry(&self) -> &'r ChecksumEntry {
+        self.entry
+    }
+
+    #[must_use]
+    pub const fn status(&self) -> &VerificationStatus {
+        &self.status
+    }
+}
+
+/// Outcomes of verifying the entries of a checksum file, in file order.
+#[must_use]
+#[derive(Debug)]
+pub struct VerificationReport<'r> {
+    entries: Vec<EntryReport<'r>>,
+}
+
+impl<'r> VerificationReport<'r> {
+    #[must_use]
+    pub fn entries(&self) -> &[EntryReport<'r>] {
+        &self.entries
+    }
+
+    /// Returns whether every entry was matched.
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.entries
+            .iter()
+            .all(|report| matches!(report.status, VerificationStatus::Matched))
+    }
+
+    #[must_use]
+    pub fn mismatched(&self) -> usize {
+        self.count(|status| matches!(status, VerificationStatus::Mismatched))
+    }
+
+    #[must_use]
+    pub fn missing(&self) -> usize {
+        self.count(|status| matches!(status, VerificationStatus::Missing))
+    }
+
+    #[must_use]
+    pub fn unreadable(&self) -> usize {
+        self.count(|status| matches!(status, VerificationStatus::Unreadable(_)))
+    }
+
+    fn count<F>(&self, mut predicate: F) -> usize
+    where
+        F: FnMut(&VerificationStatus) -> bool,
+    {
+        self.entries
+            .iter()
+            .filter(|report| predicate(&report.status))
+            .count()
+    }
+}
+
+/// Escapes a file name the way coreutils does, returning [`None`] when no
+/// escaping is needed. Lines holding escaped names are prefixed with a
+/// backslash.
+#[must_use]
+pub fn escape_file_name(file_name: &str) -> Option<String> {
+    if !file_name.contains(['\\', '\n', '\r']) {
+        return None;
+    }
+
+    let mut escaped = String::with_capacity(file_name.len() + 2);
+
+    for character in file_name.chars() {
+        match character {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            _ => escaped.push(character),
+        }
+    }
+
+    Some(escaped)
+}
+
+fn unescape_file_name(file_name: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(file_name.len());
+
+    let mut characters = file_name.chars();
+
+    while let Some(character) = characters.next() {
+        unescaped.push(if character == '\\' {
+            match characters.next()? {
+                '\\' => '\\',
+                'n' => '\n',
+                'r' => '\r',
+                _ => return None,
+            }
+        } else {
+            character
+        });
+    }
+
+    Some(unescaped)
+}
+
+/// Hashes everything read from the reader, returning the number of bytes read
+/// along with the digest.
+pub(crate) fn hash_reader<R>(algorithm: Algorithm, mut reader: R) -> io::Result<(u64, Vec<u8>)>
+where
+    R: Read,
+{
+    let mut buffer = vec![0; READ_BUFFER_SIZE];
+
+    let mut sha = AnySha2::new(algorithm);
+
+    let mut length = 0;
+
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        };
+
+        sha = sha.update(&buffer[..read]).map_err(too_long)?;
+
+        length += read as u64;
+    }
+
+    Ok((length, sha.finalize().as_bytes().to_vec()))
+}
+
+fn too_long(error: MessageTooLong) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, error)
+}
@@ -0,0 +1,199 @@
+//! Comparison of digests and MAC tags in time independent of their contents.
+//!
+//! Comparing secret-dependent values with `==` may return as soon as the first
+//! differing byte is found, leaking its position through timing. The functions
+//! here always inspect every byte and only branch on the lengths.
+//!
+//! The implementations are plain Rust, thus the absence of branches is not
+//! guaranteed after optimization. The `subtle` feature additionally provides
+//! `ct_eq_choice`, which relies on the optimization barriers of the
+//! [`subtle`](https://docs.rs/subtle) crate.
+
+use crate::error::DigestMismatch;
+
+/// Folds the bitwise differences of every pair of elements, without exiting
+/// early. Shared with the counting harness of the tests.
+macro_rules! fold_differences {
+    ($left: expr, $right: expr, $zero: expr) => {{
+        let (left, right) = ($left, $right);
+
+        let mut difference = $zero;
+
+        let mut index = 0;
+
+        while index < left.len() {
+            difference = difference | (left[index] ^ right[index]);
+
+            index += 1;
+        }
+
+        difference
+    }};
+}
+
+/// Returns whether two digests of the same size are equal, inspecting every
+/// byte regardless of where they differ.
+///
+/// Being a `const fn`, it can also compare digests while compiling.
+#[must_use]
+pub const fn ct_eq<const N: usize>(left: &[u8; N], right: &[u8; N]) -> bool {
+    fold_differences!(left, right, 0) == 0
+}
+
+/// Returns whether two byte slices are equal, inspecting every byte when their
+/// lengths match.
+///
+/// The lengths are not considered secret and are compared first.
+#[must_use]
+pub const fn ct_eq_slices(left: &[u8], right: &[u8]) -> bool {
+    if left.len() != right.len() {
+        return false;
+    }
+
+    fold_differences!(left, right, 0) == 0
+}
+
+/// Checks a computed digest or MAC tag against the expected one with
+/// [`ct_eq`].
+///
+/// # Errors
+///
+/// This function will return an error if the values differ.
+pub const fn verify<const N: usize>(
+    computed: &[u8; N],
+    expected: &[u8; N],
+) -> Result<(), DigestMismatch> {
+    if ct_eq(computed, expected) {
+        Ok(())
+    } else {
+        Err(DigestMismatch)
+    }
+}
+
+/// Compares two digests of the same size with [`subtle::ConstantTimeEq`].
+#[cfg(feature = "subtle")]
+#[must_use]
+pub fn ct_eq_choice<const N: usize>(left: &[u8; N], right: &[u8; N]) -> subtle::Choice {
+    subtle::ConstantTimeEq::ct_eq(&left[..], &right[..])
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{
+        cell::Cell,
+        ops::{BitOr, BitXor},
+    };
+
+    /// Byte counting every operation applied to it.
+    #[derive(Clone, Copy)]
+    struct Counted<'r> {
+        value: u8,
+        operations: &'r Cell<usize>,
+    }
+
+    impl Counted<'_> {
+        fn count(self) {
+            self.operations.set(self.operations.get() + 1);
+        }
+    }
+
+    impl BitXor for Counted<'_> {
+        type Output = Self;
+
+        fn bitxor(self, other: Self) -> Self {
+            self.count();
+
+            Self {
+                value: self.value ^ other.value,
+                ..self
+            }
+        }
+    }
+
+    impl BitOr for Counted<'_> {
+        type Output = Self;
+
+        fn bitor(self, other: Self) -> Self {
+            self.count();
+
+            Self {
+                value: self.value | other.value,
+                ..self
+            }
+        }
+    }
+
+    fn count_operations(left: [u8; 32], right: [u8; 32]) -> (usize, bool) {
+        let operations = Cell::new(0);
+
+        let counted = |value| Counted {
+            value,
+            operations: &operations,
+        };
+
+        let difference = fold_differences!(left.map(counted), right.map(counted), counted(0));
+
+        (operations.get(), difference.value == 0)
+    }
+
+    #[test]
+    fn test_no_early_exit() {
+        let reference = [0xA5; 32];
+
+        let (expected_operations, equal) = count_operations(reference, reference);
+
+        assert!(equal);
+
+        assert_eq!(expected_operations, 2 * 32);
+
+        for position in 0..32 {
+            for flipped_bits in [0x01, 0x80, 0xFF] {
+                let mut other = reference;
+
+                other[position] ^= flipped_bits;
+
+                assert_eq!(
+                    count_operations(reference, other),
+                    (expected_operations, false),
+                );
+            }
+        }
+
+        assert_eq!(
+            count_operations(reference, [0x5A; 32]),
+            (expected_operations, false),
+        );
+    }
+
+    #[test]
+    fn test_ct_eq() {
+        const _: () = assert!(super::ct_eq(b"abc", b"abc"));
+
+        let mut last_differs = [0; 64];
+
+        last_differs[63] = 1;
+
+        assert!(!super::ct_eq(&[0; 64], &last_differs));
+
+        assert!(super::ct_eq_slices(b"abc", b"abc"));
+
+        assert!(!super::ct_eq_slices(b"abc", b"abd"));
+
+        assert!(!super::ct_eq_slices(b"abc", b"ab"));
+
+        assert_eq!(super::verify(&[1; 28], &[1; 28]), Ok(()));
+
+        assert_eq!(
+            super::verify(&[1; 48], &[2; 48]),
+            Err(crate::error::DigestMismatch),
+        );
+    }
+
+    #[cfg(feature = "subtle")]
+    #[test]
+    fn test_ct_eq_choice() {
+        assert!(bool::from(super::ct_eq_choice(&[7; 32], &[7; 32])));
+
+        assert!(!bool::from(super::ct_eq_choice(&[7; 32], &[8; 32])));
+    }
+}
@@ -0,0 +1,214 @@
+//! Hexadecimal and base64 encodings of digests.
+//!
+//! Decoding is implemented with `const fn`s, allowing pinned digests to be
+//! parsed and checked while compiling.
+
+use core::fmt::{Display, Formatter, LowerHex, Result as FmtResult, UpperHex};
+
+use crate::error::ParseError;
+
+/// Displays bytes as hexadecimal digits, lowercase unless formatted with `{:X}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hex<'r>(pub &'r [u8]);
+
+impl Display for Hex<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        LowerHex::fmt(self, f)
+    }
+}
+
+impl LowerHex for Hex<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+impl UpperHex for Hex<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02X}"))
+    }
+}
+
+/// Decodes exactly `N` bytes from hexadecimal digits of either case.
+///
+/// # Errors
+///
+/// This function will return an error if the text is not exactly `2 * N`
+/// characters long or contains a character which is not a hexadecimal digit.
+pub const fn decode_hex<const N: usize>(text: &str) -> Result<[u8; N], ParseError> {
+    let text = text.as_bytes();
+
+    if text.len() != 2 * N {
+        return Err(ParseError::InvalidLength {
+            expected: 2 * N,
+            actual: text.len(),
+        });
+    }
+
+    let mut output = [0; N];
+
+    let mut index = 0;
+
+    while index < N {
+        let Some(high) = hex_digit_value(text[2 * index]) else {
+            return Err(ParseError::InvalidCharacter {
+                position: 2 * index,
+            });
+        };
+
+        let Some(low) = hex_digit_value(text[2 * index + 1]) else {
+            return Err(ParseError::InvalidCharacter {
+                position: 2 * index + 1,
+            });
+        };
+
+        output[index] = high << 4 | low;
+
+        index += 1;
+    }
+
+    Ok(output)
+}
+
+/// Decodes hexadecimal digits of either case, filling the whole output buffer.
+///
+/// Unlike [`decode_hex`], the length of the output is only known at runtime.
+///
+/// # Errors
+///
+/// This function will return an error if the text is not exactly twice as long
+/// as the output buffer or contains a character which is not a hexadecimal digit.
+pub fn decode_hex_into(text: &str, output: &mut [u8]) -> Result<(), ParseError> {
+    let text = text.as_bytes();
+
+    if text.len() != 2 * output.len() {
+        return Err(ParseError::InvalidLength {
+            expected: 2 * output.len(),
+            actual: text.len(),
+        });
+    }
+
+    for (index, (digits, byte)) in text.chunks_exact(2).zip(output).enumerate() {
+        let (Some(high), Some(low)) = (hex_digit_value(digits[0]), hex_digit_value(digits[1]))
+        else {
+            let position = if hex_digit_value(digits[0]).is_none() {
+                2 * index
+            } else {
+                2 * index + 1
+            };
+
+            return Err(ParseError::InvalidCharacter { position });
+        };
+
+        *byte = high << 4 | low;
+    }
+
+    Ok(())
+}
+
+/// Decodes exactly `N` bytes from standard base64, with or without padding.
+///
+/// # Errors
+///
+/// This function will return an error if the text does not encode exactly `N`
+/// bytes, contains a character outside of the standard alphabet or has
+/// non-zero trailing bits.
+pub const fn decode_base64<const N: usize>(text: &str) -> Result<[u8; N], ParseError> {
+    let text = text.as_bytes();
+
+    let unpadded_length = (4 * N + 2) / 3;
+
+    let padded_length = 4 * ((N + 2) / 3);
+
+    let mut length = text.len();
+
+    if length == padded_length {
+        while length > unpadded_length {
+            if text[length - 1] != b'=' {
+                return Err(ParseError::InvalidCharacter {
+                    position: length - 1,
+                });
+            }
+
+            length -= 1;
+        }
+    } else if length != unpadded_length {
+        return Err(ParseError::InvalidLength {
+            expected: padded_length,
+            actual: length,
+        });
+    }
+
+    let mut output = [0; N];
+
+    let mut accumulator: u32 = 0;
+
+    let mut accumulated_bits = 0;
+
+    let mut output_index = 0;
+
+    let mut index = 0;
+
+    while index < length {
+        let Some(value) = base64_digit_value(text[index]) else {
+            return Err(ParseError::InvalidCharacter { position: index });
+        };
+
+        accumulator = (accumulator << 6 | value as u32) & 0xFFFF;
+
+        accumulated_bits += 6;
+
+        if accumulated_bits >= 8 {
+            accumulated_bits -= 8;
+
+            output[output_index] = (accumulator >> accumulated_bits).to_le_bytes()[0];
+
+            output_index += 1;
+        }
+
+        index += 1;
+    }
+
+    if accumulator & ((1 << accumulated_bits) - 1) != 0 {
+        return Err(ParseError::InvalidCharacter {
+            position: length - 1,
+        });
+    }
+
+    Ok(output)
+}
+
+/// Decodes a digest of `N` bytes written either as hexadecimal digits or as
+/// base64, telling the two apart by the length of the text.
+///
+/// # Errors
+///
+/// This function will return an error if the text is neither a valid
+/// hexadecimal nor a valid base64 encoding of `N` bytes.
+pub const fn decode_digest<const N: usize>(text: &str) -> Result<[u8; N], ParseError> {
+    if text.len() == 2 * N {
+        decode_hex(text)
+    } else {
+        decode_base64(text)
+    }
+}
+
+pub(crate) const fn hex_digit_value(character: u8) -> Option<u8> {
+    Some(match character {
+        b'0'..=b'9' => character - b'0',
+        b'a'..=b'f' => character - b'a' + 10,
+        b'A'..=b'F' => character - b'A' + 10,
+        _ => return None,
+    })
+}
+
+const fn base64_digit_value(character: u8) -> Option<u8> {
+    Some(match character {
+        b'A'..=b'Z' => character - b'A',
+        b'a'..=b'z' => character - b'a' + 26,
+        b'0'..=b'9' => character - b'0' + 52,
+        b'+' => 62,
+        b'/' => 63,
+        _ => return None,
+    })
+}
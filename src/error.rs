@@ -1,6 +1,13 @@
 use core::fmt::{Display, Formatter, Result as FmtResult};
 
-#[derive(Debug)]
+use crate::algorithm::Algorithm;
+
+/// Error returned when appending to a message would exceed the length limit
+/// of the hashing function.
+///
+/// The hashers' `try_update` methods report the same failure as
+/// [`Error::MessageTooLong`], along with the algorithm and the lengths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct MessageTooLong;
 
 impl Display for MessageTooLong {
@@ -9,5 +16,360 @@ impl Display for MessageTooLong {
     }
 }
 
-#[cfg(feature = "std")]
-impl std::error::Error for MessageTooLong {}
+/// Algorithm and lengths involved when appending to a message exceeded the
+/// length limit, as reported by the hashers' `try_update` methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageTooLongDetails {
+    pub algorithm: Algorithm,
+    /// Number of bytes processed before the failing call.
+    pub committed_bytes: u128,
+    /// Number of bytes the failing call attempted to append.
+    pub attempted_bytes: usize,
+}
+
+/// Error returned when a computed digest or MAC tag differs from the expected one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DigestMismatch;
+
+impl Display for DigestMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str("Computed digest does not match the expected one!")
+    }
+}
+
+/// Error returned when textual or binary input cannot be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseError {
+    InvalidLength { expected: usize, actual: usize },
+    InvalidCharacter { position: usize },
+    UnknownAlgorithm,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::InvalidLength { expected, actual } => {
+                write!(
+                    f,
+                    "Invalid input length! Expected {expected}, got {actual}."
+                )
+            }
+            Self::InvalidCharacter { position } => {
+                write!(f, "Invalid character at position {position}!")
+            }
+            Self::UnknownAlgorithm => f.write_str("Unknown SHA2 algorithm name!"),
+        }
+    }
+}
+
+/// Error returned when a previously exported hasher state cannot be imported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StateImportError {
+    AlgorithmMismatch {
+        expected: Algorithm,
+        found: Algorithm,
+    },
+    InvalidLength {
+        expected: usize,
+        actual: usize,
+    },
+    MessageLengthOutOfRange,
+    UnknownAlgorithm {
+        tag: u8,
+    },
+}
+
+impl Display for StateImportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::AlgorithmMismatch { expected, found } => write!(
+                f,
+                "Exported state belongs to the {found} algorithm, expected {expected}!"
+            ),
+            Self::InvalidLength { expected, actual } => write!(
+                f,
+                "Invalid exported state length! Expected {expected}, got {actual}."
+            ),
+            Self::MessageLengthOutOfRange => {
+                f.write_str("Exported message length exceeds the limit of the algorithm!")
+            }
+            Self::UnknownAlgorithm { tag } => {
+                write!(f, "Exported state has the unknown algorithm tag {tag}!")
+            }
+        }
+    }
+}
+
+/// Error returned when an abbreviated digest does not identify a single
+/// digest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PrefixError {
+    NotFound,
+    Ambiguous { candidates: usize },
+}
+
+impl Display for PrefixError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::NotFound => f.write_str("No digest starts with the prefix!"),
+            Self::Ambiguous { candidates } => {
+                write!(
+                    f,
+                    "Prefix is ambiguous! {candidates} digests start with it."
+                )
+            }
+        }
+    }
+}
+
+/// Error returned by key derivation functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum KdfError {
+    OutputTooLong { maximum: usize, requested: usize },
+    ZeroIterations,
+}
+
+impl Display for KdfError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::OutputTooLong { maximum, requested } => write!(
+                f,
+                "Requested output of {requested} bytes exceeds the maximum of {maximum}!"
+            ),
+            Self::ZeroIterations => f.write_str("Iteration count must not be zero!"),
+        }
+    }
+}
+
+/// Error returned when a block header fails its proof-of-work checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ProofOfWorkError {
+    NegativeTarget,
+    TargetOverflow,
+    ZeroTarget,
+    TargetAboveLimit,
+    InsufficientWork,
+}
+
+impl Display for ProofOfWorkError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(match self {
+            Self::NegativeTarget => "Compact target is negative!",
+            Self::TargetOverflow => "Compact target does not fit into 256 bits!",
+            Self::ZeroTarget => "Target is zero!",
+            Self::TargetAboveLimit => "Target is above the proof-of-work limit!",
+            Self::InsufficientWork => "Block hash is above the target!",
+        })
+    }
+}
+
+/// Error returned when computing the Merkle root of a block's transactions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MerkleError {
+    NoTransactions,
+    /// Two sibling nodes are equal, so that a different list of transactions
+    /// has the same root (CVE-2012-2459).
+    Mutated,
+}
+
+impl Display for MerkleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(match self {
+            Self::NoTransactions => "Merkle tree has no transactions!",
+            Self::Mutated => "Merkle tree has equal siblings, its root is ambiguous!",
+        })
+    }
+}
+
+/// Error returned when minting or verifying a Hashcash stamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HashcashError {
+    Malformed,
+    UnsupportedVersion,
+    /// A field contains a colon, which separates the fields of a stamp.
+    InvalidField,
+    InsufficientBits {
+        claimed: u32,
+        required: u32,
+    },
+    InsufficientWork,
+}
+
+impl Display for HashcashError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Malformed => f.write_str("Stamp is malformed!"),
+            Self::UnsupportedVersion => f.write_str("Stamp version is not supported!"),
+            Self::InvalidField => f.write_str("Stamp field must not contain a colon!"),
+            Self::InsufficientBits { claimed, required } => write!(
+                f,
+                "Stamp claims {claimed} bits, but {required} are required!"
+            ),
+            Self::InsufficientWork => f.write_str("Stamp hash does not have the claimed bits!"),
+        }
+    }
+}
+
+/// Error returned when generating or verifying a proof of a transparency log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ProofError {
+    IndexOutOfRange { index: u64, size: u64 },
+    SizeOutOfRange { size: u64, maximum: u64 },
+    InvalidProofLength,
+    RootMismatch,
+}
+
+impl Display for ProofError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::IndexOutOfRange { index, size } => {
+                write!(f, "Leaf {index} is out of a tree of {size} leaves!")
+            }
+            Self::SizeOutOfRange { size, maximum } => {
+                write!(f, "Tree size {size} exceeds the maximum of {maximum}!")
+            }
+            Self::InvalidProofLength => f.write_str("Proof has an invalid number of hashes!"),
+            Self::RootMismatch => f.write_str("Proof does not lead to the expected root!"),
+        }
+    }
+}
+
+/// Error type covering every failure reported by the crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// Appending to the message would exceed the length limit of the
+    /// algorithm. The details are only known when returned by `try_update`,
+    /// not when converted from [`MessageTooLong`].
+    MessageTooLong(Option<MessageTooLongDetails>),
+    DigestMismatch(DigestMismatch),
+    Parse(ParseError),
+    StateImport(StateImportError),
+    Kdf(KdfError),
+    Prefix(PrefixError),
+    ProofOfWork(ProofOfWorkError),
+    Merkle(MerkleError),
+    Hashcash(HashcashError),
+    Proof(ProofError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::MessageTooLong(None) => Display::fmt(&MessageTooLong, f),
+            Self::MessageTooLong(Some(MessageTooLongDetails {
+                algorithm,
+                committed_bytes,
+                attempted_bytes,
+            })) => write!(
+                f,
+                "Message too long to be processed by the {algorithm} algorithm! Appending {attempted_bytes} bytes to the {committed_bytes} already committed exceeds its limit.",
+            ),
+            Self::DigestMismatch(error) => Display::fmt(error, f),
+            Self::Parse(error) => Display::fmt(error, f),
+            Self::StateImport(error) => Display::fmt(error, f),
+            Self::Kdf(error) => Display::fmt(error, f),
+            Self::Prefix(error) => Display::fmt(error, f),
+            Self::ProofOfWork(error) => Display::fmt(error, f),
+            Self::Merkle(error) => Display::fmt(error, f),
+            Self::Hashcash(error) => Display::fmt(error, f),
+            Self::Proof(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+impl From<MessageTooLong> for Error {
+    fn from(_: MessageTooLong) -> Self {
+        Self::MessageTooLong(None)
+    }
+}
+
+impl From<DigestMismatch> for Error {
+    fn from(error: DigestMismatch) -> Self {
+        Self::DigestMismatch(error)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Self::Parse(error)
+    }
+}
+
+impl From<StateImportError> for Error {
+    fn from(error: StateImportError) -> Self {
+        Self::StateImport(error)
+    }
+}
+
+impl From<KdfError> for Error {
+    fn from(error: KdfError) -> Self {
+        Self::Kdf(error)
+    }
+}
+
+impl From<PrefixError> for Error {
+    fn from(error: PrefixError) -> Self {
+        Self::Prefix(error)
+    }
+}
+
+impl From<ProofOfWorkError> for Error {
+    fn from(error: ProofOfWorkError) -> Self {
+        Self::ProofOfWork(error)
+    }
+}
+
+impl From<MerkleError> for Error {
+    fn from(error: MerkleError) -> Self {
+        Self::Merkle(error)
+    }
+}
+
+impl From<HashcashError> for Error {
+    fn from(error: HashcashError) -> Self {
+        Self::Hashcash(error)
+    }
+}
+
+impl From<ProofError> for Error {
+    fn from(error: ProofError) -> Self {
+        Self::Proof(error)
+    }
+}
+
+/// Implements the standard error trait, from `core` when available and
+/// otherwise from `std`.
+macro_rules! impl_error_trait {
+    ($($error: ty),+ $(,)?) => {
+        $(
+            #[cfg(feature = "core-error")]
+            impl core::error::Error for $error {}
+
+            #[cfg(all(feature = "std", not(feature = "core-error")))]
+            impl std::error::Error for $error {}
+        )+
+    };
+}
+
+impl_error_trait!(
+    MessageTooLong,
+    DigestMismatch,
+    ParseError,
+    StateImportError,
+    PrefixError,
+    KdfError,
+    ProofOfWorkError,
+    MerkleError,
+    HashcashError,
+    ProofError,
+    Error,
+);
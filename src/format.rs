@@ -0,0 +1,168 @@
+//! Hashing of formatted text without an intermediate buffer.
+//!
+//! ```
+//! use core::fmt::Write;
+//!
+//! use compile_time_sha2::{format::FmtHasher, Sha256};
+//!
+//! let mut hasher = FmtHasher::<Sha256>::new();
+//!
+//! write!(hasher, "{}:{}", "user", 42).unwrap();
+//!
+//! assert_eq!(hasher.finalize(), Sha256::digest(b"user:42"));
+//! ```
+
+use core::fmt::{Result as FmtResult, Write};
+
+use crate::{error::MessageTooLong, Sha2Algorithm};
+
+/// Adapter implementing [`Write`] for a hasher, feeding the formatted text to
+/// its `update` method.
+///
+/// Exceeding the length limit of the algorithm is reported as [`core::fmt::Error`],
+/// which carries no details; the [`MessageTooLong`] error is kept and returned
+/// by [`Self::finalize`] and [`Self::into_inner`]. Writes after a failure are
+/// rejected.
+#[must_use]
+#[derive(Debug, Clone, Copy)]
+pub struct FmtHasher<H> {
+    /// [`None`] once an update failed.
+    hasher: Option<H>,
+    error: Option<MessageTooLong>,
+}
+
+impl<H> FmtHasher<H>
+where
+    H: Sha2Algorithm,
+{
+    pub fn new() -> Self {
+        Self::from_hasher(H::new())
+    }
+
+    /// Wraps a hasher, e.g. one which was already updated with a prefix.
+    pub const fn from_hasher(hasher: H) -> Self {
+        Self {
+            hasher: Some(hasher),
+            error: None,
+        }
+    }
+
+    /// Returns the error of the failed update, if any.
+    #[must_use]
+    pub const fn error(&self) -> Option<MessageTooLong> {
+        self.error
+    }
+
+    /// Returns the wrapped hasher.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if any of the writes exceeded the
+    /// length limit of the algorithm.
+    pub fn into_inner(self) -> Result<H, MessageTooLong> {
+        match (self.hasher, self.error) {
+            (Some(hasher), _) => Ok(hasher),
+            (None, Some(error)) => Err(error),
+            (None, None) => unreachable!("hasher is only taken when an update fails"),
+        }
+    }
+
+    /// Finalizes the wrapped hasher.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if any of the writes exceeded the
+    /// length limit of the algorithm.
+    pub fn finalize(self) -> Result<H::Output, MessageTooLong> {
+        self.into_inner().map(H::finalize)
+    }
+}
+
+impl<H> Default for FmtHasher<H>
+where
+    H: Sha2Algorithm,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H> From<H> for FmtHasher<H>
+where
+    H: Sha2Algorithm,
+{
+    fn from(hasher: H) -> Self {
+        Self::from_hasher(hasher)
+    }
+}
+
+impl<H> Write for FmtHasher<H>
+where
+    H: Sha2Algorithm,
+{
+    fn write_str(&mut self, text: &str) -> FmtResult {
+        let hasher = self.hasher.take().ok_or(core::fmt::Error)?;
+
+        match hasher.update(text.as_bytes()) {
+            Ok(hasher) => {
+                self.hasher = Some(hasher);
+
+                Ok(())
+            }
+            Err(error) => {
+                self.error = Some(error);
+
+                Err(core::fmt::Error)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Write;
+
+    use super::FmtHasher;
+    use crate::{error::MessageTooLong, Sha256, Sha512};
+
+    #[test]
+    fn test_write() {
+        let mut hasher = FmtHasher::<Sha512>::new();
+
+        for (index, name) in ["alpha", "beta", ""].into_iter().enumerate() {
+            writeln!(hasher, "{index:>3} {name:?} {:#x}", index * 255).unwrap();
+        }
+
+        assert_eq!(hasher.error(), None);
+
+        assert_eq!(
+            hasher.finalize(),
+            Sha512::digest(b"  0 \"alpha\" 0x0\n  1 \"beta\" 0xff\n  2 \"\" 0x1fe\n"),
+        );
+
+        let mut hasher = FmtHasher::from(Sha256::new().update(b"prefix ").unwrap());
+
+        write!(hasher, "{}", 1.5).unwrap();
+
+        assert_eq!(hasher.finalize(), Sha256::digest(b"prefix 1.5"));
+    }
+
+    #[test]
+    fn test_message_too_long() {
+        // One byte below the limit of 2^61 bytes.
+        let almost_full = Sha256 {
+            message_length: [u32::MAX, (1 << 29) - 1],
+            ..Sha256::new()
+        };
+
+        let mut hasher = FmtHasher::from(almost_full);
+
+        assert!(write!(hasher, "ab").is_err());
+
+        assert_eq!(hasher.error(), Some(MessageTooLong));
+
+        assert!(hasher.write_str("").is_err());
+
+        assert_eq!(hasher.finalize().unwrap_err(), MessageTooLong);
+    }
+}
@@ -0,0 +1,424 @@
+//! Proof of work by searching a nonce whose SHA-256 digest, appended to a
+//! prefix, starts with a number of zero bits.
+//!
+//! A [`NonceSearch`] processes the prefix once and resumes from its state for
+//! every attempt, which then costs one or two compressions. Stamps of the
+//! Hashcash version 1 format, `1:bits:date:resource:extension:rand:counter`,
+//! are hashed with SHA-256 rather than SHA-1:
+//!
+//! ```
+//! use compile_time_sha2::hashcash::{verify, NonceSearch};
+//!
+//! let search = NonceSearch::new(b"prefix", 8).unwrap();
+//!
+//! let nonce = search.search(0..u64::MAX).unwrap();
+//!
+//! assert!(search.is_valid(nonce));
+//!
+//! # #[cfg(feature = "std")]
+//! # {
+//! use compile_time_sha2::hashcash::mint;
+//!
+//! let stamp = mint("alice@example.com", 12).unwrap();
+//!
+//! assert_eq!(verify(&stamp, 12).unwrap().resource, "alice@example.com");
+//! # }
+//! ```
+//!
+//! Checking the date and the resource of a stamp, and that it was not spent
+//! before, is left to the caller.
+
+use core::ops::Range;
+#[cfg(feature = "std")]
+use std::{
+    hash::{BuildHasher, Hash, Hasher},
+    num::NonZeroUsize,
+    sync::atomic::{AtomicU64, Ordering},
+    time::SystemTime,
+};
+
+use crate::{
+    error::{HashcashError, MessageTooLong},
+    internals::slice::split_at,
+    Sha256,
+};
+
+/// Largest number of leading zero bits of a SHA-256 digest.
+pub const MAX_BITS: u32 = 256;
+
+/// Returns the number of leading zero bits of the digest.
+#[must_use]
+pub const fn leading_zero_bits(digest: &[u8]) -> u32 {
+    let mut bits = 0;
+
+    let mut index = 0;
+
+    while index < digest.len() {
+        if digest[index] != 0 {
+            return bits + digest[index].leading_zeros();
+        }
+
+        bits += 8;
+
+        index += 1;
+    }
+
+    bits
+}
+
+/// How nonces are appended to the prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NonceEncoding {
+    /// Eight big-endian bytes.
+    BigEndian,
+    /// Lowercase hexadecimal digits without leading zeroes, as in the counter
+    /// of a Hashcash stamp.
+    LowerHex,
+}
+
+impl NonceEncoding {
+    /// Returns the encoded nonce in the first bytes of the array, and their
+    /// number.
+    const fn encode(self, nonce: u64) -> ([u8; 16], usize) {
+        let mut bytes = [0; 16];
+
+        match self {
+            Self::BigEndian => {
+                let nonce = nonce.to_be_bytes();
+
+                let mut index = 0;
+
+                while index < nonce.len() {
+                    bytes[index] = nonce[index];
+
+                    index += 1;
+                }
+
+                (bytes, nonce.len())
+            }
+            Self::LowerHex => {
+                let digits = if nonce == 0 {
+                    1
+                } else {
+                    ((u64::BITS - nonce.leading_zeros() + 3) / 4) as usize
+                };
+
+                let mut index = 0;
+
+                while index < digits {
+                    let value = (nonce >> (4 * (digits - 1 - index))).to_le_bytes()[0] & 0x0F;
+
+                    bytes[index] = if value < 10 {
+                        b'0' + value
+                    } else {
+                        b'a' + value - 10
+                    };
+
+                    index += 1;
+                }
+
+                (bytes, digits)
+            }
+        }
+    }
+}
+
+/// Search of a nonce such that `SHA256(prefix || nonce)` has at least `bits`
+/// leading zero bits.
+#[must_use]
+#[derive(Debug, Clone, Copy)]
+pub struct NonceSearch {
+    prefixed: Sha256,
+    bits: u32,
+    encoding: NonceEncoding,
+}
+
+impl NonceSearch {
+    /// Processes the prefix, nonces being encoded as
+    /// [`NonceEncoding::BigEndian`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the prefix followed by a nonce
+    /// exceeds the length limit of SHA-256.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` exceeds [`MAX_BITS`].
+    pub const fn new(prefix: &[u8], bits: u32) -> Result<Self, MessageTooLong> {
+        assert!(bits <= MAX_BITS, "SHA-256 digests have at most 256 bits!");
+
+        let prefixed = match Sha256::new().update(prefix) {
+            Ok(prefixed) => prefixed,
+            Err(error) => return Err(error),
+        };
+
+        // Appending the longest nonce must not exceed the limit either.
+        if let Err(error) = prefixed.update(&[0; 16]) {
+            return Err(error);
+        }
+
+        Ok(Self {
+            prefixed,
+            bits,
+            encoding: NonceEncoding::BigEndian,
+        })
+    }
+
+    pub const fn with_encoding(self, encoding: NonceEncoding) -> Self {
+        Self { encoding, ..self }
+    }
+
+    #[must_use]
+    pub const fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Returns `SHA256(prefix || nonce)`.
+    #[must_use]
+    pub const fn digest(&self, nonce: u64) -> [u8; 32] {
+        let (bytes, length) = self.encoding.encode(nonce);
+
+        let Ok(sha) = self.prefixed.update(split_at(&bytes, length).0) else {
+            unreachable!()
+        };
+
+        sha.finalize()
+    }
+
+    #[must_use]
+    pub const fn is_valid(&self, nonce: u64) -> bool {
+        leading_zero_bits(&self.digest(nonce)) >= self.bits
+    }
+
+    /// Returns the lowest valid nonce of the range.
+    #[must_use]
+    pub fn search(&self, nonces: Range<u64>) -> Option<u64> {
+        nonces.into_iter().find(|nonce| self.is_valid(*nonce))
+    }
+
+    /// Returns the lowest valid nonce of the range, like [`Self::search`],
+    /// spreading the attempts over threads.
+    ///
+    /// Thread `i` tries every nonce whose offset from the start of the range
+    /// is `i` modulo the number of threads, and stops past the lowest nonce
+    /// found so far, thus the result does not depend on scheduling.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn search_parallel(&self, nonces: Range<u64>, threads: NonZeroUsize) -> Option<u64> {
+        // The end of a range is excluded, thus no nonce equals this value.
+        let found = AtomicU64::new(u64::MAX);
+
+        std::thread::scope(|scope| {
+            for thread in 0..threads.get() {
+                let (found, nonces) = (&found, nonces.clone());
+
+                scope.spawn(move || {
+                    for nonce in nonces.skip(thread).step_by(threads.get()) {
+                        if nonce >= found.load(Ordering::Relaxed) {
+                            break;
+                        }
+
+                        if self.is_valid(nonce) {
+                            found.fetch_min(nonce, Ordering::Relaxed);
+
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+
+        Some(found.into_inner()).filter(|nonce| *nonce != u64::MAX)
+    }
+}
+
+/// Fields of a Hashcash version 1 stamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Stamp<'r> {
+    /// Number of leading zero bits claimed by the stamp.
+    pub bits: u32,
+    /// Date of minting, formatted as `YYMMDD[hhmm[ss]]`.
+    pub date: &'r str,
+    pub resource: &'r str,
+    pub extension: &'r str,
+    pub rand: &'r str,
+    pub counter: &'r str,
+}
+
+impl<'r> Stamp<'r> {
+    /// Splits a stamp into its fields.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the version is not 1, or if the
+    /// stamp does not have seven fields or a decimal number of bits.
+    pub fn parse(stamp: &'r str) -> Result<Self, HashcashError> {
+        let mut fields = stamp.split(':');
+
+        if fields.next() != Some("1") {
+            return Err(HashcashError::UnsupportedVersion);
+        }
+
+        let (
+            Some(bits),
+            Some(date),
+            Some(resource),
+            Some(extension),
+            Some(rand),
+            Some(counter),
+            None,
+        ) = (
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+        )
+        else {
+            return Err(HashcashError::Malformed);
+        };
+
+        Ok(Self {
+            bits: bits.parse().map_err(|_| HashcashError::Malformed)?,
+            date,
+            resource,
+            extension,
+            rand,
+            counter,
+        })
+    }
+}
+
+/// Checks that the stamp claims at least `bits` leading zero bits, and that
+/// its SHA-256 digest has the claimed bits.
+///
+/// # Errors
+///
+/// This function will return an error if the stamp cannot be parsed, or
+/// fails either check.
+pub fn verify(stamp: &str, bits: u32) -> Result<Stamp<'_>, HashcashError> {
+    let parsed = Stamp::parse(stamp)?;
+
+    if parsed.bits < bits {
+        return Err(HashcashError::InsufficientBits {
+            claimed: parsed.bits,
+            required: bits,
+        });
+    }
+
+    let digest = Sha256::digest(stamp.as_bytes()).map_err(|_| HashcashError::Malformed)?;
+
+    if leading_zero_bits(&digest) < parsed.bits {
+        return Err(HashcashError::InsufficientWork);
+    }
+
+    Ok(parsed)
+}
+
+/// Mints a stamp for the resource, dated today in UTC, searching on as many
+/// threads as are available.
+///
+/// # Errors
+///
+/// This function will return an error if the resource contains a colon.
+///
+/// # Panics
+///
+/// Panics if `bits` exceeds [`MAX_BITS`], or if no counter is found.
+#[cfg(feature = "std")]
+pub fn mint(resource: &str, bits: u32) -> Result<String, HashcashError> {
+    let threads = std::thread::available_parallelism().unwrap_or(NonZeroUsize::new(1).unwrap());
+
+    mint_with(resource, bits, &today(), &random_field(), threads)
+}
+
+/// Mints a stamp with the given date and random field, whose counter is the
+/// lowest valid one in hexadecimal.
+///
+/// # Errors
+///
+/// This function will return an error if a field contains a colon.
+///
+/// # Panics
+///
+/// Panics if `bits` exceeds [`MAX_BITS`], or if no counter is found.
+#[cfg(feature = "std")]
+pub fn mint_with(
+    resource: &str,
+    bits: u32,
+    date: &str,
+    rand: &str,
+    threads: NonZeroUsize,
+) -> Result<String, HashcashError> {
+    if [resource, date, rand]
+        .iter()
+        .any(|field| field.contains(':'))
+    {
+        return Err(HashcashError::InvalidField);
+    }
+
+    let prefix = format!("1:{bits}:{date}:{resource}::{rand}:");
+
+    let search = NonceSearch::new(prefix.as_bytes(), bits)
+        .map_err(|_| HashcashError::Malformed)?
+        .with_encoding(NonceEncoding::LowerHex);
+
+    let counter = search
+        .search_parallel(0..u64::MAX, threads)
+        .expect("No counter gives enough leading zero bits!");
+
+    Ok(format!("{prefix}{counter:x}"))
+}
+
+/// Returns the current UTC date as `YYMMDD`.
+#[cfg(feature = "std")]
+fn today() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+
+    // Converts days since the epoch to a civil date, see
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = seconds / 86_400 + 719_468;
+
+    let day_of_era = days % 146_097;
+
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+
+    let shifted_month = (5 * day_of_year + 2) / 153;
+
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+
+    let (month, year_offset) = if shifted_month < 10 {
+        (shifted_month + 3, 0)
+    } else {
+        (shifted_month - 9, 1)
+    };
+
+    let year = days / 146_097 * 400 + year_of_era + year_offset;
+
+    format!("{:02}{month:02}{day:02}", year % 100)
+}
+
+/// Returns 16 hexadecimal digits which differ between calls.
+#[cfg(feature = "std")]
+fn random_field() -> String {
+    let nanoseconds = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_nanos());
+
+    // Each `RandomState` is seeded with fresh keys.
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+
+    nanoseconds.hash(&mut hasher);
+
+    let random = hasher.finish();
+
+    format!("{random:016x}")
+}
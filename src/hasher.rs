@@ -0,0 +1,162 @@
+//! Adapter for feeding [`Hash`](core::hash::Hash) types to the hashers.
+//!
+//! ```
+//! use core::hash::{BuildHasher, Hash, Hasher};
+//!
+//! use compile_time_sha2::hasher::Sha256Hasher;
+//!
+//! #[derive(Hash)]
+//! struct Release {
+//!     major: u16,
+//!     minor: u16,
+//!     yanked: bool,
+//! }
+//!
+//! let release = Release { major: 1, minor: 4, yanked: false };
+//!
+//! let mut fingerprinter = Sha256Hasher::new().build_hasher();
+//!
+//! release.hash(&mut fingerprinter);
+//!
+//! let fingerprint = fingerprinter.finish();
+//!
+//! let mut hasher = Sha256Hasher::new();
+//!
+//! release.hash(&mut hasher);
+//!
+//! assert_eq!(fingerprint.to_be_bytes(), hasher.finalize()[..8]);
+//! ```
+//!
+//! # Stability
+//!
+//! The fingerprints only depend on the bytes written by the [`Hash`] impls.
+//! Every integer is written in little-endian order, with `usize` and `isize`
+//! widened to 64 bits, so the bytes do not depend on the target. Thus the
+//! fingerprints are stable for values whose impls only write integers:
+//!
+//! - integers, [`bool`] and [`char`], written as a single integer;
+//! - `#[derive(Hash)]` structs, which write their fields in declaration order,
+//!   and enums, which write the discriminant as an `isize` before the fields;
+//! - tuples of the above, which write their elements in order.
+//!
+//! The other impls of the standard library are not guaranteed to be stable
+//! across Rust versions. As of Rust 1.81, arrays, slices, `Vec` and `VecDeque`
+//! write their length as a `usize` before the elements, byte slices being
+//! written at once, [`str`] and `String` write their bytes followed by `0xFF`,
+//! and [`Option`] is hashed as an enum. Types whose iteration order is
+//! unspecified, e.g. `HashMap`, cannot be fingerprinted reliably.
+//!
+//! [`Hash`]: core::hash::Hash
+
+use core::hash::{BuildHasher, Hasher};
+
+use crate::{Sha224, Sha256, Sha2Algorithm, Sha384, Sha512};
+
+/// Implements [`Hasher`] on top of one of the hashers, writing integers in a
+/// portable way, and [`BuildHasher`] by copying itself.
+///
+/// A hasher which was already written to thus builds hashers sharing the same
+/// prefix, e.g. for domain separation.
+///
+/// # Panics
+///
+/// Writing panics when the total length exceeds the limit of the algorithm,
+/// as [`Hasher`] cannot report errors.
+#[must_use]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sha2Hasher<H> {
+    sha: H,
+}
+
+pub type Sha224Hasher = Sha2Hasher<Sha224>;
+pub type Sha256Hasher = Sha2Hasher<Sha256>;
+pub type Sha384Hasher = Sha2Hasher<Sha384>;
+pub type Sha512Hasher = Sha2Hasher<Sha512>;
+
+macro_rules! write_integers {
+    ($($method: ident: $type: ty),+ $(,)?) => {
+        $(
+            fn $method(&mut self, value: $type) {
+                self.write(&value.to_le_bytes());
+            }
+        )+
+    };
+}
+
+impl<H> Sha2Hasher<H>
+where
+    H: Sha2Algorithm + Copy,
+{
+    pub fn new() -> Self {
+        Self::from_hasher(H::new())
+    }
+
+    /// Wraps a hasher, e.g. one which was already updated with a prefix.
+    pub const fn from_hasher(sha: H) -> Self {
+        Self { sha }
+    }
+
+    /// Returns the wrapped hasher.
+    pub const fn into_inner(self) -> H {
+        self.sha
+    }
+
+    /// Returns the full digest of the written values.
+    #[must_use]
+    pub fn finalize(self) -> H::Output {
+        self.sha.finalize()
+    }
+}
+
+impl<H> Hasher for Sha2Hasher<H>
+where
+    H: Sha2Algorithm + Copy,
+{
+    /// Returns the first 8 bytes of the digest as a big-endian integer.
+    fn finish(&self) -> u64 {
+        let digest = self.sha.finalize();
+
+        let mut bytes = [0; 8];
+
+        bytes.copy_from_slice(&digest.as_ref()[..8]);
+
+        u64::from_be_bytes(bytes)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.sha = match self.sha.update(bytes) {
+            Ok(sha) => sha,
+            Err(error) => panic!("{error}"),
+        };
+    }
+
+    write_integers![
+        write_u16: u16,
+        write_u32: u32,
+        write_u64: u64,
+        write_u128: u128,
+        write_i16: i16,
+        write_i32: i32,
+        write_i64: i64,
+        write_i128: i128,
+    ];
+
+    fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64);
+    }
+
+    fn write_isize(&mut self, value: isize) {
+        self.write_i64(value as i64);
+    }
+}
+
+impl<H> BuildHasher for Sha2Hasher<H>
+where
+    H: Sha2Algorithm + Copy,
+{
+    type Hasher = Self;
+
+    fn build_hasher(&self) -> Self {
+        *self
+    }
+}
@@ -0,0 +1,195 @@
+//! Stable identifiers derived from names, e.g. wire tags of message types.
+//!
+//! An identifier is the truncated SHA-256 digest of [`ID_DOMAIN`] followed by
+//! the UTF-8 bytes of the name, read as a big-endian integer. Both functions
+//! are `const fn`s, thus identifiers can be computed while compiling:
+//!
+//! ```
+//! use compile_time_sha2::id::id64;
+//!
+//! const PING: u64 = id64("ping");
+//!
+//! assert_eq!(PING, id64("ping"));
+//! assert_ne!(PING, id64("pong"));
+//! ```
+//!
+//! The [`stable_ids!`](crate::stable_ids!) macro generates an enum mapping its
+//! variants to identifiers, rejecting colliding names while compiling.
+
+use crate::Sha256;
+
+/// Domain separation prefix hashed before the name.
+///
+/// Part of the definition of the identifiers; changing it changes all of them.
+pub const ID_DOMAIN: &[u8] = b"compile_time_sha2/id/v1\0";
+
+/// Returns the 64-bit identifier of the name.
+#[must_use]
+pub const fn id64(name: &str) -> u64 {
+    u64::from_be_bytes(digest(name).finalize_truncated())
+}
+
+/// Returns the 128-bit identifier of the name.
+#[must_use]
+pub const fn id128(name: &str) -> u128 {
+    u128::from_be_bytes(digest(name).finalize_truncated())
+}
+
+const fn digest(name: &str) -> Sha256 {
+    let Ok(sha) = Sha256::new().update(ID_DOMAIN) else {
+        unreachable!()
+    };
+
+    match sha.update(name.as_bytes()) {
+        Ok(sha) => sha,
+        Err(_) => panic!("Name too long to be processed by the SHA-256 algorithm!"),
+    }
+}
+
+/// Generates an enum whose variants are mapped to the identifiers of their
+/// names, computed with [`id64`](crate::id::id64) or
+/// [`id128`](crate::id::id128) depending on the declared integer type.
+///
+/// Along with the enum, the macro generates:
+///
+/// - the `VARIANTS`, `NAMES` and `IDS` tables, in declaration order;
+/// - the `const fn`s `name`, `id` and `from_id`;
+/// - a check failing compilation if two names share the same identifier,
+///   which includes repeated names.
+///
+/// ```
+/// compile_time_sha2::stable_ids! {
+///     /// Kinds of the messages exchanged with the server.
+///     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///     pub enum MessageKind: u64 {
+///         Ping = "ping",
+///         Pong = "pong",
+///         /// Renamed in code, the identifier stays the same.
+///         Shutdown = "close",
+///     }
+/// }
+///
+/// use compile_time_sha2::id::id64;
+///
+/// assert_eq!(MessageKind::Pong.id(), id64("pong"));
+/// assert_eq!(MessageKind::Shutdown.name(), "close");
+/// assert_eq!(MessageKind::from_id(id64("close")), Some(MessageKind::Shutdown));
+/// assert_eq!(MessageKind::from_id(id64("reset")), None);
+/// assert_eq!(MessageKind::IDS, [id64("ping"), id64("pong"), id64("close")]);
+/// ```
+///
+/// ```compile_fail
+/// compile_time_sha2::stable_ids! {
+///     pub enum MessageKind: u64 {
+///         Ping = "ping",
+///         Pong = "ping",
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! stable_ids {
+    (
+        $(#[$meta: meta])*
+        $visibility: vis enum $name: ident: $int: ident {
+            $(
+                $(#[$variant_meta: meta])*
+                $variant: ident = $variant_name: literal
+            ),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $visibility enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant,
+            )+
+        }
+
+        impl $name {
+            /// Variants in declaration order.
+            pub const VARIANTS: &'static [Self] = &[$(Self::$variant),+];
+
+            /// Names of the variants in declaration order.
+            pub const NAMES: &'static [&'static str] = &[$($variant_name),+];
+
+            /// Identifiers of the variants in declaration order.
+            pub const IDS: &'static [$int] = &[
+                $($crate::__stable_id!($int, $variant_name)),+
+            ];
+
+            /// Returns the name the identifier is derived from.
+            #[must_use]
+            pub const fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $variant_name),+
+                }
+            }
+
+            /// Returns the identifier of the variant.
+            #[must_use]
+            pub const fn id(&self) -> $int {
+                match self {
+                    $(Self::$variant => $crate::__stable_id!($int, $variant_name)),+
+                }
+            }
+
+            /// Returns the variant with the given identifier, if any.
+            #[must_use]
+            pub const fn from_id(id: $int) -> ::core::option::Option<Self> {
+                $(
+                    if id == $crate::__stable_id!($int, $variant_name) {
+                        return ::core::option::Option::Some(Self::$variant);
+                    }
+                )+
+
+                ::core::option::Option::None
+            }
+        }
+
+        const _: () = {
+            let ids = $name::IDS;
+
+            let mut left = 0;
+
+            while left < ids.len() {
+                let mut right = left + 1;
+
+                while right < ids.len() {
+                    if ids[left] == ids[right] {
+                        panic!(concat!(
+                            "Two names of `",
+                            stringify!($name),
+                            "` share the same identifier!",
+                        ));
+                    }
+
+                    right += 1;
+                }
+
+                left += 1;
+            }
+        };
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __stable_id {
+    (u64, $name: literal) => {{
+        const ID: u64 = $crate::id::id64($name);
+
+        ID
+    }};
+    (u128, $name: literal) => {{
+        const ID: u128 = $crate::id::id128($name);
+
+        ID
+    }};
+    ($int: ident, $name: literal) => {
+        compile_error!(concat!(
+            "Identifiers are either `u64` or `u128`, not `",
+            stringify!($int),
+            "`!",
+        ))
+    };
+}
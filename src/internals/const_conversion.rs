@@ -54,10 +54,11 @@ const_conversion_to_pack![
 ];
 
 #[cfg(test)]
-#[test]
-fn test_conversions() {
+mod tests {
     use core::mem::size_of;
 
+    use super::{Array, BytesRepresentation, Zero};
+
     impl Zero for u128 {
         const ZERO: Self = 0;
     }
@@ -71,11 +72,14 @@ fn test_conversions() {
         test_u32_to_u64_2: (u32) -> ([u64; 2]),
     ];
 
-    assert_eq!(test_u128_to_u32_2(u64::MAX.into()), Some([u32::MAX; 2]));
+    #[test]
+    fn test_conversions() {
+        assert_eq!(test_u128_to_u32_2(u64::MAX.into()), Some([u32::MAX; 2]));
 
-    assert_eq!(test_u128_to_u32_2(u128::MAX), None);
+        assert_eq!(test_u128_to_u32_2(u128::MAX), None);
 
-    assert_eq!(test_u128_to_u32_2(u128::from(u32::MAX) + 1), Some([0, 1]));
+        assert_eq!(test_u128_to_u32_2(u128::from(u32::MAX) + 1), Some([0, 1]));
 
-    assert_eq!(test_u32_to_u64_2(u32::MAX), Some([u32::MAX.into(), 0]));
+        assert_eq!(test_u32_to_u64_2(u32::MAX), Some([u32::MAX.into(), 0]));
+    }
 }
@@ -5,5 +5,6 @@ pub(crate) mod digest_chunk;
 pub(crate) mod extract_output;
 pub(crate) mod message_schedule;
 pub(crate) mod prepare_state_for_next_chunk;
+pub(crate) mod slice;
 pub(crate) mod traits;
 pub(crate) mod types;
@@ -0,0 +1,43 @@
+/// Splits a slice in a `const` context, where `<[T]>::split_at` is only
+/// available since Rust 1.71.
+///
+/// Takes time linear in the length of the slice.
+///
+/// # Panics
+///
+/// Panics if `mid` exceeds the length of the slice.
+pub(crate) const fn split_at<T>(slice: &[T], mid: usize) -> (&[T], &[T]) {
+    assert!(mid <= slice.len(), "Split point is out of the slice!");
+
+    let mut head = slice;
+
+    while head.len() > mid {
+        if let [rest @ .., _] = head {
+            head = rest;
+        }
+    }
+
+    let mut tail = slice;
+
+    while tail.len() > slice.len() - mid {
+        if let [_, rest @ ..] = tail {
+            tail = rest;
+        }
+    }
+
+    (head, tail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_at;
+
+    #[test]
+    fn test_split_at() {
+        let bytes = [1, 2, 3, 4, 5];
+
+        for mid in 0..=bytes.len() {
+            assert_eq!(split_at(&bytes, mid), bytes.split_at(mid));
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use core::mem::size_of;
+use core::{fmt::Debug, hash::Hash, mem::size_of};
+
+use crate::{algorithm::Algorithm, error::MessageTooLong};
 
 use super::types::State;
 
@@ -69,8 +71,86 @@ impl BytesRepresentation for usize {
     type ByteArray = [u8; size_of::<Self>()];
 }
 
+/// Hashing function from the SHA2 family, allowing code to be generic over
+/// the hashers.
+///
+/// The methods mirror the inherent `const fn`s of the hashers, which should be
+/// preferred when the hasher is known, e.g. in constant evaluation.
+///
+/// ```
+/// use compile_time_sha2::{error::MessageTooLong, Sha2Algorithm, Sha256};
+///
+/// fn checksum<H: Sha2Algorithm>(parts: &[&[u8]]) -> Result<H::Output, MessageTooLong> {
+///     let mut sha = H::new();
+///
+///     for part in parts {
+///         sha = sha.update(part)?;
+///     }
+///
+///     Ok(sha.finalize())
+/// }
+///
+/// assert_eq!(
+///     checksum::<Sha256>(&[b"a", b"bc"]),
+///     Sha256::digest(b"abc"),
+/// );
+/// ```
 pub trait Sha2Algorithm {
-    type Output;
+    /// Digest, an array of [`Self::OUTPUT_SIZE`] bytes.
+    type Output: AsRef<[u8]> + AsMut<[u8]> + Copy + Debug + Eq + Hash + Ord;
+
+    const ALGORITHM: Algorithm;
+
+    /// Name of the algorithm as spelled in FIPS 180-4, e.g. `SHA-256`.
+    const NAME: &'static str = Self::ALGORITHM.name();
+
+    /// Size of the blocks processed by the compression function, in bytes.
+    const BLOCK_SIZE: usize = Self::ALGORITHM.block_size();
+
+    /// Size of the digest, in bytes.
+    const OUTPUT_SIZE: usize = Self::ALGORITHM.output_length();
+
+    /// Maximum length of a message, in bits.
+    const MAX_MESSAGE_BITS: u128;
+
+    /// Object identifier of the algorithm in dotted notation, e.g.
+    /// `2.16.840.1.101.3.4.2.1`.
+    const OID: &'static str = Self::ALGORITHM.oid();
+
+    /// DER encoding of the contents of the object identifier, without the tag
+    /// and the length.
+    const OID_DER: &'static [u8] = Self::ALGORITHM.oid_der();
+
+    fn new() -> Self
+    where
+        Self: Sized;
+
+    /// See the inherent `update` method of the hashers.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the cumulative message length
+    /// exceeds [`Self::MAX_MESSAGE_BITS`].
+    fn update(self, message: &[u8]) -> Result<Self, MessageTooLong>
+    where
+        Self: Sized;
+
+    fn finalize(self) -> Self::Output
+    where
+        Self: Sized;
+
+    /// Hashes a single message in one call.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error under the same conditions as
+    /// [`Self::update`].
+    fn digest(message: &[u8]) -> Result<Self::Output, MessageTooLong>
+    where
+        Self: Sized,
+    {
+        Self::new().update(message).map(Self::finalize)
+    }
 }
 
 pub(crate) trait Sha2AlgorithmInternal: Sha2Algorithm
@@ -24,6 +24,17 @@ where
     ShaAlg::Output: Array<ElementsType = u8>,
 {
     pub(crate) const INITIAL: Self = Self([<ShaAlg::WorkingVariableT as Zero>::ZERO; CHUNK_LENGTH]);
+
+    /// Reinterprets the chunk for another algorithm using the same working variables, e.g.
+    /// SHA-224 and SHA-256.
+    pub(crate) const fn cast<OtherShaAlg>(&self) -> Chunk<OtherShaAlg>
+    where
+        ShaAlg::WorkingVariableT: Copy,
+        OtherShaAlg: Sha2AlgorithmInternal<WorkingVariableT = ShaAlg::WorkingVariableT> + ?Sized,
+        OtherShaAlg::Output: Array<ElementsType = u8>,
+    {
+        Chunk(self.0)
+    }
 }
 
 #[must_use]
@@ -41,6 +52,44 @@ where
     pub(crate) const fn new(values: [ShaAlg::WorkingVariableT; STATE_LENGTH]) -> Self {
         Self(values)
     }
+
+    pub(crate) const fn words(&self) -> [ShaAlg::WorkingVariableT; STATE_LENGTH]
+    where
+        ShaAlg::WorkingVariableT: Copy,
+    {
+        self.0
+    }
+}
+
+impl<ShaAlg> Clone for State<ShaAlg>
+where
+    ShaAlg: Sha2AlgorithmInternal + ?Sized,
+    ShaAlg::Output: Array<ElementsType = u8>,
+    ShaAlg::WorkingVariableT: Copy,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<ShaAlg> Copy for State<ShaAlg>
+where
+    ShaAlg: Sha2AlgorithmInternal + ?Sized,
+    ShaAlg::Output: Array<ElementsType = u8>,
+    ShaAlg::WorkingVariableT: Copy,
+{
+}
+
+#[cfg(feature = "zeroize")]
+impl<ShaAlg> zeroize::Zeroize for State<ShaAlg>
+where
+    ShaAlg: Sha2AlgorithmInternal + ?Sized,
+    ShaAlg::Output: Array<ElementsType = u8>,
+    ShaAlg::WorkingVariableT: zeroize::Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
 }
 
 #[must_use]
@@ -61,7 +110,7 @@ where
 
 #[must_use]
 #[repr(transparent)]
-pub struct RoundKeys<ShaAlg, const ROUND_COUNT: usize>(
+pub(crate) struct RoundKeys<ShaAlg, const ROUND_COUNT: usize>(
     pub(super) [ShaAlg::WorkingVariableT; ROUND_COUNT],
 )
 where
@@ -1,16 +1,18 @@
 #![forbid(warnings, unsafe_code, clippy::pedantic)]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use core::fmt::{Debug, Formatter, Result as FmtResult};
+
 use self::{
+    algorithm::Algorithm,
     consts::{
-        SHA2_224_256_ROUND_COUNT, SHA2_224_256_ROUND_KEYS, SHA2_224_OUTPUT_LENGTH,
-        SHA2_256_OUTPUT_LENGTH, SHA2_384_512_ROUND_COUNT, SHA2_384_512_ROUND_KEYS,
-        SHA2_384_OUTPUT_LENGTH, SHA2_512_OUTPUT_LENGTH,
+        SHA2_224_256_ROUND_COUNT, SHA2_224_256_ROUND_KEYS, SHA2_384_512_ROUND_COUNT,
+        SHA2_384_512_ROUND_KEYS,
     },
-    error::MessageTooLong,
+    error::{MessageTooLong, MessageTooLongDetails, StateImportError},
     internals::{
         const_conversion::{usize_to_message_length_le_u32, usize_to_message_length_le_u64},
-        consts::CHUNK_LENGTH,
+        consts::{CHUNK_LENGTH, STATE_LENGTH},
         convert_to_chunk::{u32_impl as convert_to_chunk_u32, u64_impl as convert_to_chunk_u64},
         digest_chunk::{u32_impl as digest_chunk_u32, u64_impl as digest_chunk_u64},
         extract_output::{u32_impl as extract_output_u32, u64_impl as extract_output_u64},
@@ -19,19 +21,83 @@ use self::{
             u32_impl as prepare_state_for_next_chunk_u32,
             u64_impl as prepare_state_for_next_chunk_u64,
         },
-        traits::{Array, BytesRepresentation, Sha2Algorithm, Sha2AlgorithmInternal, Zero},
+        traits::{Array, BytesRepresentation, Sha2AlgorithmInternal, UnsignedInteger, Zero},
         types::{Buffer, Chunk, MessageLength, RoundKeys, State},
     },
+    state::ExportedState,
+};
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+pub use self::{
+    any::{AnyDigest, AnySha2},
+    constant_time::ct_eq,
+    format::FmtHasher,
+    hasher::Sha2Hasher,
+    internals::traits::Sha2Algorithm,
+    multi::{MultiDigest, Sha2Multi},
+    self_test::self_test,
+    structured::Sha2Hash,
 };
 
+pub mod algorithm;
+pub mod any;
+pub mod bitcoin;
+#[cfg(feature = "std")]
+pub mod build;
+#[cfg(feature = "std")]
+pub mod checksums;
+pub mod constant_time;
 pub mod consts;
+pub mod encoding;
 pub mod error;
+pub mod format;
+pub mod hashcash;
+pub mod hasher;
+pub mod id;
 mod internals;
+pub mod multi;
+pub mod prefix;
+pub mod self_test;
+#[cfg(feature = "serde")]
+pub mod serialization;
+pub mod state;
+pub mod structured;
+pub mod transparency;
+mod verify;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::constant_time::ct_eq;
+}
+
+/// Compile-time check that a message of `N` bytes fits below `LIMIT`, referenced in place of an
+/// inline `const` block, which needs Rust 1.79.
+struct MessageLengthCheck<const N: usize, const LIMIT: u128>;
+
+impl<const N: usize, const LIMIT: u128> MessageLengthCheck<N, LIMIT> {
+    const OK: () = assert!(
+        (N as u128) < LIMIT,
+        "Message too long to be processed by the SHA2 algorithm!",
+    );
+}
+
+/// Compile-time check that `N` bytes can be taken from a digest of `LENGTH` bytes.
+struct TruncationCheck<const N: usize, const LENGTH: usize>;
+
+impl<const N: usize, const LENGTH: usize> TruncationCheck<N, LENGTH> {
+    const OK: () = assert!(
+        N <= LENGTH,
+        "Truncated length exceeds the length of the digest!",
+    );
+}
 
 macro_rules! sha2 {
     (
         $(
             $sha_type: ident {
+                word: $word: ty,
                 usize_to_message_length_le: $usize_to_message_length_le: expr,
                 convert_to_chunk: $convert_to_chunk: ident,
                 message_schedule: $message_schedule: ident,
@@ -44,7 +110,15 @@ macro_rules! sha2 {
         $(,)?
     ) => {
         $(
+            /// Copying the hasher forks the running digest, e.g. to finalize a snapshot of it, as
+            /// done by [`Self::peek_finalize`].
+            ///
+            /// Every copy holds the secret state of the message, which the `zeroize` feature only
+            /// wipes in the copy being finalized or zeroized. As passing the hasher by value also
+            /// copies it, the bindings it was passed from must be zeroized as well, or the hasher
+            /// kept in a `zeroize::Zeroizing` and only used through references.
             #[must_use]
+            #[derive(Clone, Copy)]
             pub struct $sha_type {
                 message_length: MessageLength<Self>,
                 buffer_position: usize,
@@ -52,12 +126,73 @@ macro_rules! sha2 {
                 state: State<Self>,
             }
 
+            impl Default for $sha_type {
+                fn default() -> Self {
+                    Self::new()
+                }
+            }
+
+            /// Only shows the length of the message, leaving out the buffered message and the
+            /// state.
+            impl Debug for $sha_type {
+                fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+                    f.debug_struct(stringify!($sha_type))
+                        .field("message_length", &self.committed_message_length())
+                        .finish_non_exhaustive()
+                }
+            }
+
+            impl Sha2Algorithm for $sha_type {
+                type Output = [u8; Algorithm::$sha_type.output_length()];
+
+                const ALGORITHM: Algorithm = Algorithm::$sha_type;
+
+                const MAX_MESSAGE_BITS: u128 = (Self::MESSAGE_LENGTH_LIMIT - 1) * 8;
+
+                fn new() -> Self {
+                    Self::new()
+                }
+
+                fn update(self, message: &[u8]) -> Result<Self, MessageTooLong> {
+                    Self::update(self, message)
+                }
+
+                fn finalize(self) -> Self::Output {
+                    Self::finalize(self)
+                }
+            }
+
+            #[cfg(feature = "zeroize")]
+            /// Overwrites the buffered message, the state and the message length with zeroes,
+            /// leaving the hasher unusable until it is reassigned.
+            ///
+            /// The hashers do not implement [`Drop`], as it would prevent matching on the result of
+            /// [`Self::update`] in constant evaluation. Hashers which are dropped without being
+            /// finalized can instead be wrapped in [`zeroize::Zeroizing`].
+            ///
+            /// Only this copy is overwritten: the hashers are [`Copy`], thus copies made before,
+            /// including by passing the hasher by value, must be zeroized on their own.
+            impl Zeroize for $sha_type {
+                fn zeroize(&mut self) {
+                    self.message_length.zeroize();
+
+                    self.buffer_position.zeroize();
+
+                    self.buffer.zeroize();
+
+                    self.state.zeroize();
+                }
+            }
+
             impl $sha_type {
                 const WORKING_VARIABLE_SIZE: usize =
                     <<<Self as Sha2AlgorithmInternal>::WorkingVariableT as BytesRepresentation>::ByteArray as Array>::LENGTH;
 
                 const CHUNK_SIZE: usize = CHUNK_LENGTH * Self::WORKING_VARIABLE_SIZE;
 
+                /// Number of state words making up the digest.
+                const OUTPUT_WORDS: usize = Algorithm::$sha_type.output_length() / Self::WORKING_VARIABLE_SIZE;
+
                 /// Constant representing the size in bytes of the length field in the final block of the digest.
                 const LENGTH_FIELD_SIZE: usize =
                     <<<MessageLength<Self> as Array>::ElementsType as BytesRepresentation>::ByteArray as Array>::LENGTH
@@ -65,6 +200,11 @@ macro_rules! sha2 {
 
                 const LENGTH_FIELD_START: usize = Self::CHUNK_SIZE - Self::LENGTH_FIELD_SIZE;
 
+                /// Exclusive upper bound of the message length in bytes, leaving space for the
+                /// multiplication by 8 when the length is encoded in bits.
+                const MESSAGE_LENGTH_LIMIT: u128 =
+                    1 << (2 * <<Self as Sha2AlgorithmInternal>::WorkingVariableT as UnsignedInteger>::BITS - 3);
+
                 pub const fn new() -> Self {
                     Self {
                         message_length: Zero::ZERO,
@@ -74,6 +214,54 @@ macro_rules! sha2 {
                     }
                 }
 
+                /// Hashes a single message in one call.
+                ///
+                /// # Errors
+                ///
+                /// This function will return an error under the same conditions as [`Self::update`].
+                pub const fn digest(message: &[u8]) -> Result<<Self as Sha2Algorithm>::Output, MessageTooLong> {
+                    match Self::new().update(message) {
+                        Ok(sha) => Ok(sha.finalize()),
+                        Err(error) => Err(error),
+                    }
+                }
+
+                /// Hashes the concatenation of the given parts in one call.
+                ///
+                /// # Errors
+                ///
+                /// This function will return an error under the same conditions as [`Self::update`].
+                pub const fn digest_parts(parts: &[&[u8]]) -> Result<<Self as Sha2Algorithm>::Output, MessageTooLong> {
+                    let mut sha = Self::new();
+
+                    let mut index = 0;
+
+                    while index < parts.len() {
+                        sha = match sha.update(parts[index]) {
+                            Ok(sha) => sha,
+                            Err(error) => return Err(error),
+                        };
+
+                        index += 1;
+                    }
+
+                    Ok(sha.finalize())
+                }
+
+                /// Hashes a fixed-size message.
+                ///
+                /// The length of the message is checked against the limits of the hashing function
+                /// while compiling, thus no error can be returned.
+                #[must_use]
+                pub const fn digest_array<const N: usize>(message: &[u8; N]) -> <Self as Sha2Algorithm>::Output {
+                    let () = MessageLengthCheck::<N, { $sha_type::MESSAGE_LENGTH_LIMIT }>::OK;
+
+                    match Self::digest(message) {
+                        Ok(output) => output,
+                        Err(_) => unreachable!(),
+                    }
+                }
+
                 /// Appends the message in chunks, processing them when the internal buffer is full,
                 /// thus the chunk is ready to be digested by the hashing function.
                 ///
@@ -85,13 +273,34 @@ macro_rules! sha2 {
                 /// The limit for SHA224/-256 is: 2^61.
                 ///
                 /// The limit for SHA384/-512 is: 2^125.
-                pub const fn update(mut self, message: &[u8]) -> Result<Self, MessageTooLong> {
+                pub const fn update(self, message: &[u8]) -> Result<Self, MessageTooLong> {
+                    match self.try_update(message) {
+                        Ok(sha) => Ok(sha),
+                        Err(_) => Err(MessageTooLong),
+                    }
+                }
+
+                /// Same as [`Self::update`], but reports the algorithm and the lengths involved
+                /// when the limit is exceeded.
+                ///
+                /// # Errors
+                ///
+                /// This function will return [`Error::MessageTooLong`] under the same conditions
+                /// as [`Self::update`].
+                ///
+                /// [`Error::MessageTooLong`]: error::Error::MessageTooLong
+                pub const fn try_update(mut self, message: &[u8]) -> Result<Self, error::Error> {
                     Ok(if message.is_empty() {
                         self
                     } else {
                         self.message_length = match self.calculate_new_message_length(message) {
                             Ok(new_message_length) => new_message_length,
-                            Err(error) => return Err(error),
+                            Err(error) => {
+                                #[cfg(feature = "zeroize")]
+                                self.clear();
+
+                                return Err(error);
+                            }
                         };
 
                         self.update_buffer(message)
@@ -99,7 +308,97 @@ macro_rules! sha2 {
                 }
 
                 #[must_use]
-                pub const fn finalize(mut self) -> <Self as Sha2Algorithm>::Output {
+                pub const fn finalize(self) -> <Self as Sha2Algorithm>::Output {
+                    self.finalize_with_leftover().0
+                }
+
+                /// Same as [`Self::finalize`], also returning the hasher as left behind, which is
+                /// cleared with the `zeroize` feature.
+                const fn finalize_with_leftover(mut self) -> (<Self as Sha2Algorithm>::Output, Self) {
+                    self = self.pad();
+
+                    let output = $extract_output(&self.state);
+
+                    #[cfg(feature = "zeroize")]
+                    self.clear();
+
+                    (output, self)
+                }
+
+                /// Returns the first `N` bytes of the digest, e.g. for SHA-256/192.
+                ///
+                /// `N` is checked against the length of the digest while compiling.
+                #[must_use]
+                pub const fn finalize_truncated<const N: usize>(self) -> [u8; N] {
+                    let () = TruncationCheck::<N, { Algorithm::$sha_type.output_length() }>::OK;
+
+                    let output = self.finalize();
+
+                    let mut truncated = [0; N];
+
+                    let mut index = 0;
+
+                    while index < N {
+                        truncated[index] = output[index];
+
+                        index += 1;
+                    }
+
+                    truncated
+                }
+
+                /// Writes the first `output.len()` bytes of the digest into `output`, which
+                /// receives the whole digest when its length is the output length.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `output` is longer than the digest.
+                pub fn finalize_into(self, output: &mut [u8]) {
+                    assert!(
+                        output.len() <= Algorithm::$sha_type.output_length(),
+                        "Output buffer is longer than the digest!",
+                    );
+
+                    let words = self.finalize_words();
+
+                    for (bytes, word) in output.chunks_mut(Self::WORKING_VARIABLE_SIZE).zip(words) {
+                        bytes.copy_from_slice(&word.to_be_bytes()[..bytes.len()]);
+                    }
+                }
+
+                /// Returns the digest as big-endian words, i.e. the words of the final state which
+                /// make up the output, without serializing them to bytes.
+                #[must_use]
+                pub const fn finalize_words(self) -> [$word; Self::OUTPUT_WORDS] {
+                    self.finalize_words_with_leftover().0
+                }
+
+                /// Same as [`Self::finalize_words`], also returning the hasher as left behind,
+                /// which is cleared with the `zeroize` feature.
+                const fn finalize_words_with_leftover(mut self) -> ([$word; Self::OUTPUT_WORDS], Self) {
+                    self = self.pad();
+
+                    let state = self.state.words();
+
+                    let mut words = [0; Self::OUTPUT_WORDS];
+
+                    let mut index = 0;
+
+                    while index < Self::OUTPUT_WORDS {
+                        words[index] = state[index];
+
+                        index += 1;
+                    }
+
+                    #[cfg(feature = "zeroize")]
+                    self.clear();
+
+                    (words, self)
+                }
+
+                /// Appends the padding and the length of the message, processing the final
+                /// chunks, after which the state holds the digest.
+                const fn pad(mut self) -> Self {
                     self.buffer[(self.buffer_position / Self::WORKING_VARIABLE_SIZE) % CHUNK_LENGTH]
                         [self.buffer_position % Self::WORKING_VARIABLE_SIZE] = 0x80;
 
@@ -126,7 +425,7 @@ macro_rules! sha2 {
                             let [lower_half, higher_half] = self.message_length;
 
                             self.buffer[CHUNK_LENGTH - 2] = (higher_half << 3
-                                | lower_half >> (<Self as Sha2AlgorithmInternal>::WorkingVariableT::BITS - 3))
+                                | lower_half >> (<<Self as Sha2AlgorithmInternal>::WorkingVariableT as UnsignedInteger>::BITS - 3))
                                 .to_be_bytes();
 
                             self.buffer[CHUNK_LENGTH - 1] = (lower_half << 3).to_be_bytes();
@@ -135,7 +434,7 @@ macro_rules! sha2 {
                         self.state = Self::convert_and_process_chunk(self.state, &self.buffer);
 
                         if zero_until != Self::CHUNK_SIZE {
-                            break $extract_output(&self.state);
+                            break self;
                         }
 
                         zero_until = self.buffer_position;
@@ -144,13 +443,118 @@ macro_rules! sha2 {
                     }
                 }
 
+                /// Returns the digest of the message so far, leaving the hasher untouched so that
+                /// it can be updated further.
+                #[must_use]
+                pub const fn peek_finalize(&self) -> <Self as Sha2Algorithm>::Output {
+                    let snapshot = *self;
+
+                    snapshot.finalize()
+                }
+
+                /// Exports the running state, which [`Self::import_state`] resumes from.
+                pub const fn export_state(&self) -> ExportedState {
+                    let mut exported = ExportedState::new(Algorithm::$sha_type, self.committed_message_length());
+
+                    let state = self.state.words();
+
+                    let mut index = 0;
+
+                    while index < STATE_LENGTH {
+                        exported = exported.push(&state[index].to_be_bytes());
+
+                        index += 1;
+                    }
+
+                    index = 0;
+
+                    while index < self.buffer_position {
+                        exported = exported.push(&[self.buffer[index / Self::WORKING_VARIABLE_SIZE][index % Self::WORKING_VARIABLE_SIZE]]);
+
+                        index += 1;
+                    }
+
+                    exported
+                }
+
+                /// Resumes hashing from a state exported by [`Self::export_state`].
+                ///
+                /// # Errors
+                ///
+                /// This function will return an error if the state was exported by a hasher of
+                /// another algorithm.
+                pub const fn import_state(exported: &ExportedState) -> Result<Self, StateImportError> {
+                    let found = exported.algorithm();
+
+                    if !matches!(found, Algorithm::$sha_type) {
+                        return Err(StateImportError::AlgorithmMismatch {
+                            expected: Algorithm::$sha_type,
+                            found,
+                        });
+                    }
+
+                    let message_length = exported.message_length().to_le_bytes();
+
+                    let mut lower_half = [0; Self::WORKING_VARIABLE_SIZE];
+
+                    let mut higher_half = [0; Self::WORKING_VARIABLE_SIZE];
+
+                    let mut index = 0;
+
+                    while index < Self::WORKING_VARIABLE_SIZE {
+                        lower_half[index] = message_length[index];
+
+                        higher_half[index] = message_length[Self::WORKING_VARIABLE_SIZE + index];
+
+                        index += 1;
+                    }
+
+                    let mut state = [0; STATE_LENGTH];
+
+                    index = 0;
+
+                    while index < STATE_LENGTH {
+                        state[index] = <$word>::from_be_bytes(exported.state_bytes(index * Self::WORKING_VARIABLE_SIZE));
+
+                        index += 1;
+                    }
+
+                    let sha = Self {
+                        message_length: [<$word>::from_le_bytes(lower_half), <$word>::from_le_bytes(higher_half)],
+                        buffer_position: 0,
+                        buffer: Zero::ZERO,
+                        state: State::new(state),
+                    };
+
+                    Ok(sha.fill_buffer(exported.buffered(), 0).0)
+                }
+
+                /// Overwrites the buffered message, the state and the message length.
+                ///
+                /// The writes are not volatile, thus the hasher is passed through
+                /// [`core::hint::black_box`] to keep them from being optimized out, which is only
+                /// usable in constant evaluation since Rust 1.86.
+                #[cfg(feature = "zeroize")]
+                #[clippy::msrv = "1.86"]
+                const fn clear(&mut self) {
+                    self.message_length = Zero::ZERO;
+
+                    self.buffer_position = 0;
+
+                    self.buffer = Zero::ZERO;
+
+                    self.state = State::new(Zero::ZERO);
+
+                    core::hint::black_box(self);
+                }
+
                 #[inline]
                 const fn calculate_new_message_length(
                     &self,
                     message: &[u8],
-                ) -> Result<[<Self as Sha2AlgorithmInternal>::WorkingVariableT; 2], MessageTooLong> {
+                ) -> Result<[<Self as Sha2AlgorithmInternal>::WorkingVariableT; 2], error::Error> {
                     let Some(appending_length) = $usize_to_message_length_le(message.len()) else {
-                        return Err(MessageTooLong);
+                        return Err(self.message_too_long(message));
                     };
 
                     let (
@@ -163,14 +567,14 @@ macro_rules! sha2 {
                     let Some(intermediate_higher_half) =
                         committed_higher_half.checked_add(appending_higher_half)
                     else {
-                        return Err(MessageTooLong);
+                        return Err(self.message_too_long(message));
                     };
 
                     let new_higher_half = if overflow {
                         if let Some(new_higher_half) = intermediate_higher_half.checked_add(1) {
                             new_higher_half
                         } else {
-                            return Err(MessageTooLong);
+                            return Err(self.message_too_long(message));
                         }
                     } else {
                         intermediate_higher_half
@@ -179,53 +583,82 @@ macro_rules! sha2 {
                     // Three most-significant bits are zeroes, allowing safe multiplication by 8,
                     // which in turn represent the number of bits in the digested message.
                     if new_higher_half.leading_zeros() < 3 {
-                        return Err(MessageTooLong);
+                        return Err(self.message_too_long(message));
                     }
 
                     Ok([new_lower_half, new_higher_half])
                 }
 
+                #[cold]
+                const fn message_too_long(&self, message: &[u8]) -> error::Error {
+                    error::Error::MessageTooLong(Some(MessageTooLongDetails {
+                        algorithm: Algorithm::$sha_type,
+                        committed_bytes: self.committed_message_length(),
+                        attempted_bytes: message.len(),
+                    }))
+                }
+
+                /// Returns the length in bytes of the message appended so far.
+                const fn committed_message_length(&self) -> u128 {
+                    let [committed_lower_half, committed_higher_half] = self.message_length;
+
+                    committed_lower_half as u128
+                        | (committed_higher_half as u128)
+                            << <<Self as Sha2AlgorithmInternal>::WorkingVariableT as UnsignedInteger>::BITS
+                }
+
                 #[inline]
                 /// # Assumptions
                 /// This method assumes, and is optimized for, the case of a non-empty message.
                 const fn update_buffer(mut self, message: &[u8]) -> Self {
                     let mut source_index = 0;
 
-                    let mut outer_buffer_index = self.buffer_position / Self::WORKING_VARIABLE_SIZE;
+                    loop {
+                        (self, source_index) = self.fill_buffer(message, source_index);
 
-                    let mut inner_buffer_index = self.buffer_position % Self::WORKING_VARIABLE_SIZE;
+                        if self.buffer_position != Self::CHUNK_SIZE {
+                            break self;
+                        }
 
-                    'outmost_loop: loop {
-                        while outer_buffer_index < CHUNK_LENGTH {
-                            while inner_buffer_index < Self::WORKING_VARIABLE_SIZE {
-                                if source_index == message.len() {
-                                    break 'outmost_loop;
-                                }
+                        self.state = Self::convert_and_process_chunk(self.state, &self.buffer);
 
-                                self.buffer[outer_buffer_index][inner_buffer_index] = message[source_index];
+                        self.buffer_position = 0;
+                    }
+                }
 
-                                self.buffer_position += 1;
+                /// Copies the message into the buffer, starting from `source_index`, until either
+                /// the message is exhausted or the buffer is full, returning the index of the first
+                /// byte which was not copied.
+                ///
+                /// A full buffer is left for the caller to process, allowing [`Sha2Multi`] to share
+                /// it between algorithms with the same block size.
+                #[inline]
+                const fn fill_buffer(mut self, message: &[u8], mut source_index: usize) -> (Self, usize) {
+                    let mut outer_buffer_index = self.buffer_position / Self::WORKING_VARIABLE_SIZE;
 
-                                source_index += 1;
+                    let mut inner_buffer_index = self.buffer_position % Self::WORKING_VARIABLE_SIZE;
 
-                                inner_buffer_index += 1;
+                    while outer_buffer_index < CHUNK_LENGTH {
+                        while inner_buffer_index < Self::WORKING_VARIABLE_SIZE {
+                            if source_index == message.len() {
+                                return (self, source_index);
                             }
 
-                            inner_buffer_index = 0;
-
-                            outer_buffer_index += 1;
-                        }
+                            self.buffer[outer_buffer_index][inner_buffer_index] = message[source_index];
 
-                        if outer_buffer_index == CHUNK_LENGTH {
-                            self.state = Self::convert_and_process_chunk(self.state, &self.buffer);
+                            self.buffer_position += 1;
 
-                            self.buffer_position = 0;
+                            source_index += 1;
 
-                            outer_buffer_index = 0;
+                            inner_buffer_index += 1;
                         }
+
+                        inner_buffer_index = 0;
+
+                        outer_buffer_index += 1;
                     }
 
-                    self
+                    (self, source_index)
                 }
 
                 #[inline]
@@ -292,6 +725,7 @@ macro_rules! sha2 {
 
 sha2![
     Sha224 {
+        word: u32,
         usize_to_message_length_le: usize_to_message_length_le_u32,
         convert_to_chunk: convert_to_chunk_u32,
         message_schedule: message_schedule_u32,
@@ -300,6 +734,7 @@ sha2![
         extract_output: extract_output_u32,
     },
     Sha256 {
+        word: u32,
         usize_to_message_length_le: usize_to_message_length_le_u32,
         convert_to_chunk: convert_to_chunk_u32,
         message_schedule: message_schedule_u32,
@@ -308,6 +743,7 @@ sha2![
         extract_output: extract_output_u32,
     },
     Sha384 {
+        word: u64,
         usize_to_message_length_le: usize_to_message_length_le_u64,
         convert_to_chunk: convert_to_chunk_u64,
         message_schedule: message_schedule_u64,
@@ -316,6 +752,7 @@ sha2![
         extract_output: extract_output_u64,
     },
     Sha512 {
+        word: u64,
         usize_to_message_length_le: usize_to_message_length_le_u64,
         convert_to_chunk: convert_to_chunk_u64,
         message_schedule: message_schedule_u64,
@@ -325,10 +762,6 @@ sha2![
     },
 ];
 
-impl Sha2Algorithm for Sha224 {
-    type Output = [u8; SHA2_224_OUTPUT_LENGTH];
-}
-
 impl Sha2AlgorithmInternal for Sha224 {
     type WorkingVariableT = <Sha256 as Sha2AlgorithmInternal>::WorkingVariableT;
 
@@ -376,10 +809,6 @@ impl Sha2AlgorithmInternal for Sha224 {
         <Sha256 as Sha2AlgorithmInternal>::DIGEST_CHUNK_VARIABLE_4_ROT3;
 }
 
-impl Sha2Algorithm for Sha256 {
-    type Output = [u8; SHA2_256_OUTPUT_LENGTH];
-}
-
 impl Sha2AlgorithmInternal for Sha256 {
     type WorkingVariableT = u32;
 
@@ -415,10 +844,6 @@ impl Sha2AlgorithmInternal for Sha256 {
     const DIGEST_CHUNK_VARIABLE_4_ROT3: u32 = 25;
 }
 
-impl Sha2Algorithm for Sha384 {
-    type Output = [u8; SHA2_384_OUTPUT_LENGTH];
-}
-
 impl Sha2AlgorithmInternal for Sha384 {
     type WorkingVariableT = <Sha512 as Sha2AlgorithmInternal>::WorkingVariableT;
 
@@ -466,10 +891,6 @@ impl Sha2AlgorithmInternal for Sha384 {
         <Sha512 as Sha2AlgorithmInternal>::DIGEST_CHUNK_VARIABLE_4_ROT3;
 }
 
-impl Sha2Algorithm for Sha512 {
-    type Output = [u8; SHA2_512_OUTPUT_LENGTH];
-}
-
 impl Sha2AlgorithmInternal for Sha512 {
     type WorkingVariableT = u64;
 
@@ -504,3 +925,91 @@ impl Sha2AlgorithmInternal for Sha512 {
     const DIGEST_CHUNK_VARIABLE_4_ROT2: u32 = 18;
     const DIGEST_CHUNK_VARIABLE_4_ROT3: u32 = 41;
 }
+
+#[cfg(test)]
+#[test]
+fn test_message_too_long_details() {
+    let sha = Sha256 {
+        message_length: [u32::MAX, (1 << 29) - 1],
+        ..Sha256::new()
+    };
+
+    assert_eq!(sha.update(b"0").err(), Some(MessageTooLong));
+
+    assert_eq!(
+        sha.try_update(b"0").err(),
+        Some(error::Error::MessageTooLong(Some(MessageTooLongDetails {
+            algorithm: Algorithm::Sha256,
+            committed_bytes: (1 << 61) - 1,
+            attempted_bytes: 1,
+        }))),
+    );
+
+    assert!(matches!(sha.update(b"0"), Err(MessageTooLong)));
+
+    let propagate = |sha: Sha256| -> Result<Sha256, error::Error> { Ok(sha.update(b"0")?) };
+
+    assert_eq!(
+        propagate(sha).err(),
+        Some(error::Error::MessageTooLong(None)),
+    );
+
+    assert!(propagate(Sha256::new()).is_ok());
+}
+
+#[cfg(all(test, feature = "zeroize"))]
+#[test]
+fn test_clear() {
+    let mut sha = Sha256::new().update(&[0xA5; 100]).unwrap();
+
+    sha.clear();
+
+    assert_eq!(sha.message_length, [0; 2]);
+
+    assert_eq!(sha.buffer_position, 0);
+
+    assert_eq!(sha.buffer, [[0; 4]; CHUNK_LENGTH]);
+
+    assert_eq!(
+        extract_output_u32(&sha.state),
+        [0; consts::SHA2_256_OUTPUT_LENGTH]
+    );
+}
+
+#[cfg(all(test, feature = "zeroize"))]
+#[test]
+fn test_finalize_clears() {
+    let sha = Sha256::new().update(&[0xA5; 100]).unwrap();
+
+    let (output, leftover) = sha.finalize_with_leftover();
+
+    assert_eq!(output, sha.finalize());
+
+    assert_eq!(leftover.message_length, [0; 2]);
+
+    assert_eq!(leftover.buffer_position, 0);
+
+    assert_eq!(leftover.buffer, [[0; 4]; CHUNK_LENGTH]);
+
+    assert_eq!(
+        extract_output_u32(&leftover.state),
+        [0; consts::SHA2_256_OUTPUT_LENGTH]
+    );
+
+    let sha = Sha512::new().update(&[0xA5; 100]).unwrap();
+
+    let (words, leftover) = sha.finalize_words_with_leftover();
+
+    assert_eq!(words, sha.finalize_words());
+
+    assert_eq!(leftover.message_length, [0; 2]);
+
+    assert_eq!(leftover.buffer_position, 0);
+
+    assert_eq!(leftover.buffer, [[0; 8]; CHUNK_LENGTH]);
+
+    assert_eq!(
+        extract_output_u64(&leftover.state),
+        [0; consts::SHA2_512_OUTPUT_LENGTH]
+    );
+}
@@ -0,0 +1,454 @@
+//! Hashing with several algorithms in a single pass over the message.
+//!
+//! SHA-224 and SHA-256, as well as SHA-384 and SHA-512, share their block size
+//! and round function, differing only in the initial state and the length of
+//! the output. The message is thus buffered and converted to blocks once for
+//! each pair, only the compression function running once per algorithm.
+//!
+//! ```
+//! use compile_time_sha2::{algorithm::Algorithm, Sha256, Sha2Multi, Sha512};
+//!
+//! let digests = Sha2Multi::new(&[Algorithm::Sha256, Algorithm::Sha512])
+//!     .update(b"release")
+//!     .unwrap()
+//!     .update(b" artifact")
+//!     .unwrap()
+//!     .finalize();
+//!
+//! assert_eq!(digests.sha256(), Some(Sha256::digest(b"release artifact").unwrap()));
+//! assert_eq!(digests.sha512(), Some(Sha512::digest(b"release artifact").unwrap()));
+//! assert_eq!(digests.sha384(), None);
+//! ```
+
+use core::fmt::{Debug, Formatter, Result as FmtResult};
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+#[cfg(feature = "zeroize")]
+use crate::internals::traits::Zero;
+use crate::{
+    algorithm::Algorithm,
+    any::AnyDigest,
+    consts::{
+        SHA2_224_OUTPUT_LENGTH, SHA2_256_OUTPUT_LENGTH, SHA2_384_OUTPUT_LENGTH,
+        SHA2_512_OUTPUT_LENGTH,
+    },
+    error::MessageTooLong,
+    internals::{
+        convert_to_chunk::{u32_impl as convert_to_chunk_u32, u64_impl as convert_to_chunk_u64},
+        types::{Chunk, State},
+    },
+    Sha224, Sha256, Sha384, Sha512,
+};
+
+macro_rules! lane {
+    (
+        $(
+            $(#[$attribute: meta])*
+            $lane: ident {
+                smaller: $smaller: ident,
+                larger: $larger: ident,
+                convert_to_chunk: $convert_to_chunk: ident
+                $(,)?
+            }
+        ),+
+        $(,)?
+    ) => {
+        $(
+            $(#[$attribute])*
+            #[derive(Clone, Copy)]
+            enum $lane {
+                Idle,
+                Smaller($smaller),
+                Larger($larger),
+                /// The message is buffered by the hasher of the larger algorithm, only the state of
+                /// the smaller one being kept alongside.
+                Both($larger, State<$smaller>),
+            }
+
+            impl $lane {
+                const fn new(smaller: bool, larger: bool) -> Self {
+                    match (smaller, larger) {
+                        (false, false) => Self::Idle,
+                        (true, false) => Self::Smaller($smaller::new()),
+                        (false, true) => Self::Larger($larger::new()),
+                        (true, true) => Self::Both(
+                            $larger::new(),
+                            <$smaller as crate::internals::traits::Sha2AlgorithmInternal>::INITIAL_STATE,
+                        ),
+                    }
+                }
+
+                const fn contains(&self, smaller: bool) -> bool {
+                    matches!(
+                        (self, smaller),
+                        (Self::Smaller(_) | Self::Both(..), true) | (Self::Larger(_) | Self::Both(..), false)
+                    )
+                }
+
+                const fn update(self, message: &[u8]) -> Result<Self, MessageTooLong> {
+                    match self {
+                        Self::Idle => Ok(Self::Idle),
+                        Self::Smaller(sha) => match sha.update(message) {
+                            Ok(sha) => Ok(Self::Smaller(sha)),
+                            Err(error) => Err(error),
+                        },
+                        Self::Larger(sha) => match sha.update(message) {
+                            Ok(sha) => Ok(Self::Larger(sha)),
+                            Err(error) => Err(error),
+                        },
+                        Self::Both(larger, smaller_state) => Self::update_both(larger, smaller_state, message),
+                    }
+                }
+
+                /// Mirrors the `update` method of the hashers, converting every full buffer to a
+                /// chunk once and processing it with both states.
+                const fn update_both(
+                    mut larger: $larger,
+                    mut smaller_state: State<$smaller>,
+                    message: &[u8],
+                ) -> Result<Self, MessageTooLong> {
+                    if message.is_empty() {
+                        return Ok(Self::Both(larger, smaller_state));
+                    }
+
+                    larger.message_length = match larger.calculate_new_message_length(message) {
+                        Ok(new_message_length) => new_message_length,
+                        Err(_) => {
+                            #[cfg(feature = "zeroize")]
+                            {
+                                larger.clear();
+
+                                Self::clear_state(&mut smaller_state);
+                            }
+
+                            return Err(MessageTooLong);
+                        }
+                    };
+
+                    let mut source_index = 0;
+
+                    loop {
+                        (larger, source_index) = larger.fill_buffer(message, source_index);
+
+                        if larger.buffer_position != $larger::CHUNK_SIZE {
+                            break Ok(Self::Both(larger, smaller_state));
+                        }
+
+                        let chunk: Chunk<$larger> = $convert_to_chunk(&larger.buffer);
+
+                        larger.state = $larger::process_chunk(larger.state, &chunk);
+
+                        smaller_state = $smaller::process_chunk(smaller_state, &chunk.cast());
+
+                        larger.buffer_position = 0;
+                    }
+                }
+
+                const fn finalize(
+                    self,
+                ) -> (
+                    Option<<$smaller as crate::Sha2Algorithm>::Output>,
+                    Option<<$larger as crate::Sha2Algorithm>::Output>,
+                ) {
+                    match self {
+                        Self::Idle => (None, None),
+                        Self::Smaller(sha) => (Some(sha.finalize()), None),
+                        Self::Larger(sha) => (None, Some(sha.finalize())),
+                        Self::Both(larger, smaller_state) => {
+                            let smaller = $smaller {
+                                message_length: larger.message_length,
+                                buffer_position: larger.buffer_position,
+                                buffer: larger.buffer,
+                                state: smaller_state,
+                            };
+
+                            (Some(smaller.finalize()), Some(larger.finalize()))
+                        }
+                    }
+                }
+
+                #[cfg(feature = "zeroize")]
+                #[clippy::msrv = "1.86"]
+                const fn clear(&mut self) {
+                    match self {
+                        Self::Idle => {}
+                        Self::Smaller(sha) => sha.clear(),
+                        Self::Larger(sha) => sha.clear(),
+                        Self::Both(larger, smaller_state) => {
+                            larger.clear();
+
+                            Self::clear_state(smaller_state);
+                        }
+                    }
+                }
+
+                /// Overwrites the state of the smaller algorithm, as done by the `clear` method of
+                /// the hashers.
+                #[cfg(feature = "zeroize")]
+                #[clippy::msrv = "1.86"]
+                const fn clear_state(state: &mut State<$smaller>) {
+                    *state = State::new(Zero::ZERO);
+
+                    core::hint::black_box(state);
+                }
+            }
+
+            #[cfg(feature = "zeroize")]
+            impl Zeroize for $lane {
+                fn zeroize(&mut self) {
+                    match self {
+                        Self::Idle => {}
+                        Self::Smaller(sha) => sha.zeroize(),
+                        Self::Larger(sha) => sha.zeroize(),
+                        Self::Both(larger, smaller_state) => {
+                            larger.zeroize();
+
+                            smaller_state.zeroize();
+                        }
+                    }
+                }
+            }
+        )+
+    };
+}
+
+lane![
+    /// Hashers of the selected algorithms among SHA-224 and SHA-256.
+    Sha256Lane {
+        smaller: Sha224,
+        larger: Sha256,
+        convert_to_chunk: convert_to_chunk_u32,
+    },
+    /// Hashers of the selected algorithms among SHA-384 and SHA-512.
+    Sha512Lane {
+        smaller: Sha384,
+        larger: Sha512,
+        convert_to_chunk: convert_to_chunk_u64,
+    },
+];
+
+/// Hasher feeding the same message to a set of algorithms.
+///
+/// Algorithms sharing their block size share the buffering of the message and
+/// its conversion to blocks.
+#[must_use]
+#[derive(Clone, Copy)]
+pub struct Sha2Multi {
+    sha256_lane: Sha256Lane,
+    sha512_lane: Sha512Lane,
+}
+
+/// Digests produced by [`Sha2Multi`], one for each of the selected algorithms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MultiDigest {
+    sha224: Option<[u8; SHA2_224_OUTPUT_LENGTH]>,
+    sha256: Option<[u8; SHA2_256_OUTPUT_LENGTH]>,
+    sha384: Option<[u8; SHA2_384_OUTPUT_LENGTH]>,
+    sha512: Option<[u8; SHA2_512_OUTPUT_LENGTH]>,
+}
+
+impl Sha2Multi {
+    /// Creates a hasher for the given algorithms. Repeated algorithms are only
+    /// hashed once.
+    pub const fn new(algorithms: &[Algorithm]) -> Self {
+        let mut selected = [false; Algorithm::ALL.len()];
+
+        let mut index = 0;
+
+        while index < algorithms.len() {
+            selected[algorithms[index] as usize] = true;
+
+            index += 1;
+        }
+
+        let [sha224, sha256, sha384, sha512] = selected;
+
+        Self {
+            sha256_lane: Sha256Lane::new(sha224, sha256),
+            sha512_lane: Sha512Lane::new(sha384, sha512),
+        }
+    }
+
+    /// Hashes a single message with the given algorithms in one call.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error under the same conditions as [`Self::update`].
+    pub const fn digest(
+        algorithms: &[Algorithm],
+        message: &[u8],
+    ) -> Result<MultiDigest, MessageTooLong> {
+        match Self::new(algorithms).update(message) {
+            Ok(sha) => Ok(sha.finalize()),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Updates the hashers of every selected algorithm with the given message.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the total length of the messages
+    /// exceeds the limit of any of the selected algorithms.
+    pub const fn update(mut self, message: &[u8]) -> Result<Self, MessageTooLong> {
+        self.sha256_lane = match self.sha256_lane.update(message) {
+            Ok(sha256_lane) => sha256_lane,
+            Err(error) => {
+                #[cfg(feature = "zeroize")]
+                self.sha512_lane.clear();
+
+                return Err(error);
+            }
+        };
+
+        self.sha512_lane = match self.sha512_lane.update(message) {
+            Ok(sha512_lane) => sha512_lane,
+            Err(error) => {
+                #[cfg(feature = "zeroize")]
+                self.sha256_lane.clear();
+
+                return Err(error);
+            }
+        };
+
+        Ok(self)
+    }
+
+    /// Returns whether the given algorithm was selected.
+    #[must_use]
+    pub const fn contains(&self, algorithm: Algorithm) -> bool {
+        match algorithm {
+            Algorithm::Sha224 => self.sha256_lane.contains(true),
+            Algorithm::Sha256 => self.sha256_lane.contains(false),
+            Algorithm::Sha384 => self.sha512_lane.contains(true),
+            Algorithm::Sha512 => self.sha512_lane.contains(false),
+        }
+    }
+
+    #[must_use]
+    pub const fn finalize(self) -> MultiDigest {
+        let (sha224, sha256) = self.sha256_lane.finalize();
+
+        let (sha384, sha512) = self.sha512_lane.finalize();
+
+        MultiDigest {
+            sha224,
+            sha256,
+            sha384,
+            sha512,
+        }
+    }
+
+    /// Returns the digests of the message so far, leaving the hasher untouched
+    /// so that it can be updated further.
+    #[must_use]
+    pub const fn peek_finalize(&self) -> MultiDigest {
+        let snapshot = *self;
+
+        snapshot.finalize()
+    }
+}
+
+/// Only shows the selected algorithms, leaving out the buffered message and the
+/// states.
+impl Debug for Sha2Multi {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        /// Lists the selected algorithms without allocating.
+        struct Selected<'r>(&'r Sha2Multi);
+
+        impl Debug for Selected<'_> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+                f.debug_list()
+                    .entries(
+                        Algorithm::ALL
+                            .into_iter()
+                            .filter(|algorithm| self.0.contains(*algorithm)),
+                    )
+                    .finish()
+            }
+        }
+
+        f.debug_struct("Sha2Multi")
+            .field("algorithms", &Selected(self))
+            .finish_non_exhaustive()
+    }
+}
+
+/// Overwrites the state of every selected algorithm, as documented for the
+/// individual hashers.
+#[cfg(feature = "zeroize")]
+impl Zeroize for Sha2Multi {
+    fn zeroize(&mut self) {
+        self.sha256_lane.zeroize();
+
+        self.sha512_lane.zeroize();
+    }
+}
+
+impl MultiDigest {
+    #[must_use]
+    pub const fn sha224(&self) -> Option<[u8; SHA2_224_OUTPUT_LENGTH]> {
+        self.sha224
+    }
+
+    #[must_use]
+    pub const fn sha256(&self) -> Option<[u8; SHA2_256_OUTPUT_LENGTH]> {
+        self.sha256
+    }
+
+    #[must_use]
+    pub const fn sha384(&self) -> Option<[u8; SHA2_384_OUTPUT_LENGTH]> {
+        self.sha384
+    }
+
+    #[must_use]
+    pub const fn sha512(&self) -> Option<[u8; SHA2_512_OUTPUT_LENGTH]> {
+        self.sha512
+    }
+
+    /// Returns the digest of the given algorithm, if it was selected.
+    #[must_use]
+    pub const fn get(&self, algorithm: Algorithm) -> Option<AnyDigest> {
+        match (algorithm, self) {
+            (
+                Algorithm::Sha224,
+                Self {
+                    sha224: Some(digest),
+                    ..
+                },
+            ) => Some(AnyDigest::Sha224(*digest)),
+            (
+                Algorithm::Sha256,
+                Self {
+                    sha256: Some(digest),
+                    ..
+                },
+            ) => Some(AnyDigest::Sha256(*digest)),
+            (
+                Algorithm::Sha384,
+                Self {
+                    sha384: Some(digest),
+                    ..
+                },
+            ) => Some(AnyDigest::Sha384(*digest)),
+            (
+                Algorithm::Sha512,
+                Self {
+                    sha512: Some(digest),
+                    ..
+                },
+            ) => Some(AnyDigest::Sha512(*digest)),
+            _ => None,
+        }
+    }
+
+    /// Returns the digests of the selected algorithms, in the order of
+    /// [`Algorithm::ALL`].
+    pub fn iter(&self) -> impl Iterator<Item = AnyDigest> + '_ {
+        Algorithm::ALL
+            .into_iter()
+            .filter_map(|algorithm| self.get(algorithm))
+    }
+}
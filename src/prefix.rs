@@ -0,0 +1,259 @@
+//! Abbreviated digests, shown as their first hexadecimal digits like short
+//! commit hashes.
+//!
+//! ```
+//! use compile_time_sha2::{prefix::{unique_prefix_length, DigestPrefix}, Sha256};
+//!
+//! let mut digests = [b"alpha", b"bravo", b"gamma"].map(|name| Sha256::digest(name).unwrap());
+//!
+//! digests.sort_unstable();
+//!
+//! let length = unique_prefix_length(&digests, 7).unwrap();
+//!
+//! let prefix = DigestPrefix::from_digest(&digests[1], length);
+//!
+//! assert_eq!(prefix.len(), length);
+//! assert_eq!(prefix.resolve(&digests), Ok(&digests[1]));
+//!
+//! let parsed: DigestPrefix = prefix.to_string().parse().unwrap();
+//!
+//! assert_eq!(parsed, prefix);
+//! ```
+
+use core::{
+    fmt::{Display, Formatter, LowerHex, Result as FmtResult, UpperHex},
+    str::FromStr,
+};
+
+use crate::{
+    consts::SHA2_512_OUTPUT_LENGTH,
+    encoding::hex_digit_value,
+    error::{ParseError, PrefixError},
+};
+
+/// Maximum number of hexadecimal digits of a prefix, i.e. of a SHA-512 digest.
+pub const MAX_PREFIX_DIGITS: usize = 2 * SHA2_512_OUTPUT_LENGTH;
+
+/// First hexadecimal digits of a digest, which may be odd in number.
+#[must_use]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DigestPrefix {
+    /// Digits packed two per byte, the last one being in the high half of its
+    /// byte when their number is odd. Bytes past the digits are zeroes.
+    bytes: [u8; SHA2_512_OUTPUT_LENGTH],
+    digits: usize,
+}
+
+impl DigestPrefix {
+    /// Parses hexadecimal digits of either case.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the text is longer than
+    /// [`MAX_PREFIX_DIGITS`] or contains a character which is not a
+    /// hexadecimal digit.
+    pub const fn parse(text: &str) -> Result<Self, ParseError> {
+        let text = text.as_bytes();
+
+        if text.len() > MAX_PREFIX_DIGITS {
+            return Err(ParseError::InvalidLength {
+                expected: MAX_PREFIX_DIGITS,
+                actual: text.len(),
+            });
+        }
+
+        let mut bytes = [0; SHA2_512_OUTPUT_LENGTH];
+
+        let mut index = 0;
+
+        while index < text.len() {
+            let Some(value) = hex_digit_value(text[index]) else {
+                return Err(ParseError::InvalidCharacter { position: index });
+            };
+
+            bytes[index / 2] |= if index % 2 == 0 { value << 4 } else { value };
+
+            index += 1;
+        }
+
+        Ok(Self {
+            bytes,
+            digits: text.len(),
+        })
+    }
+
+    /// Abbreviates the digest to its first `digits` hexadecimal digits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the digest has fewer digits, or if `digits` exceeds
+    /// [`MAX_PREFIX_DIGITS`].
+    pub const fn from_digest(digest: &[u8], digits: usize) -> Self {
+        assert!(
+            digits <= 2 * digest.len() && digits <= MAX_PREFIX_DIGITS,
+            "Prefix is longer than the digest!",
+        );
+
+        let mut bytes = [0; SHA2_512_OUTPUT_LENGTH];
+
+        let mut index = 0;
+
+        while index < digits / 2 {
+            bytes[index] = digest[index];
+
+            index += 1;
+        }
+
+        if digits % 2 == 1 {
+            bytes[index] = digest[index] & 0xF0;
+        }
+
+        Self { bytes, digits }
+    }
+
+    /// Returns the number of hexadecimal digits.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.digits
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.digits == 0
+    }
+
+    /// Returns whether the digest starts with the prefix.
+    #[must_use]
+    pub const fn matches(&self, digest: &[u8]) -> bool {
+        if 2 * digest.len() < self.digits {
+            return false;
+        }
+
+        let mut index = 0;
+
+        while index < self.digits / 2 {
+            if digest[index] != self.bytes[index] {
+                return false;
+            }
+
+            index += 1;
+        }
+
+        self.digits % 2 == 0 || digest[index] & 0xF0 == self.bytes[index]
+    }
+
+    /// Finds the only digest starting with the prefix among digests sorted by
+    /// their bytes.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if no digest, or more than one,
+    /// starts with the prefix.
+    pub fn resolve<'r, T>(&self, sorted: &'r [T]) -> Result<&'r T, PrefixError>
+    where
+        T: AsRef<[u8]>,
+    {
+        // Digests starting with the prefix are not lower than its digits
+        // followed by zeroes, thus they form a run starting at the first digest
+        // which is not lower.
+        let start =
+            sorted.partition_point(|digest| digest.as_ref() < &self.bytes[..(self.digits + 1) / 2]);
+
+        let candidates = sorted[start..]
+            .iter()
+            .take_while(|digest| self.matches(digest.as_ref()))
+            .count();
+
+        match candidates {
+            0 => Err(PrefixError::NotFound),
+            1 => Ok(&sorted[start]),
+            candidates => Err(PrefixError::Ambiguous { candidates }),
+        }
+    }
+
+    const fn digit(&self, index: usize) -> u8 {
+        let byte = self.bytes[index / 2];
+
+        if index % 2 == 0 {
+            byte >> 4
+        } else {
+            byte & 0x0F
+        }
+    }
+}
+
+/// Returns the least number of hexadecimal digits, not below `minimum`, which
+/// abbreviates every digest of the set to a distinct prefix.
+///
+/// The digests must be sorted by their bytes, e.g. arrays sorted with their
+/// [`Ord`] implementation.
+///
+/// # Errors
+///
+/// This function will return an error if a digest is repeated, or starts
+/// with another digest of the set, as no prefix tells them apart.
+pub fn unique_prefix_length<T>(sorted: &[T], minimum: usize) -> Result<usize, PrefixError>
+where
+    T: AsRef<[u8]>,
+{
+    sorted.windows(2).try_fold(minimum, |length, pair| {
+        let (left, right) = (pair[0].as_ref(), pair[1].as_ref());
+
+        let common = common_digits(left, right);
+
+        if common < 2 * left.len().min(right.len()) {
+            Ok(length.max(common + 1))
+        } else {
+            let shorter = if left.len() <= right.len() {
+                left
+            } else {
+                right
+            };
+
+            let prefix = DigestPrefix::from_digest(shorter, 2 * shorter.len());
+
+            let candidates = sorted
+                .iter()
+                .filter(|digest| prefix.matches(digest.as_ref()))
+                .count();
+
+            Err(PrefixError::Ambiguous { candidates })
+        }
+    })
+}
+
+/// Returns the number of leading hexadecimal digits shared by both digests.
+fn common_digits(left: &[u8], right: &[u8]) -> usize {
+    left.iter()
+        .zip(right)
+        .position(|(left, right)| left != right)
+        .map_or(2 * left.len().min(right.len()), |index| {
+            2 * index + usize::from(left[index] >> 4 == right[index] >> 4)
+        })
+}
+
+impl FromStr for DigestPrefix {
+    type Err = ParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::parse(text)
+    }
+}
+
+impl Display for DigestPrefix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        LowerHex::fmt(self, f)
+    }
+}
+
+impl LowerHex for DigestPrefix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        (0..self.digits).try_for_each(|index| write!(f, "{:x}", self.digit(index)))
+    }
+}
+
+impl UpperHex for DigestPrefix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        (0..self.digits).try_for_each(|index| write!(f, "{:X}", self.digit(index)))
+    }
+}
@@ -0,0 +1,252 @@
+//! Power-on self-test running known-answer tests through every algorithm.
+//!
+//! The same known answers are also checked while compiling the crate, so a
+//! miscompiled constant table fails the build instead of producing wrong hashes.
+
+use core::hint::black_box;
+
+use crate::{
+    algorithm::Algorithm,
+    consts::{
+        SHA2_224_OUTPUT_LENGTH, SHA2_256_OUTPUT_LENGTH, SHA2_384_OUTPUT_LENGTH,
+        SHA2_512_OUTPUT_LENGTH,
+    },
+    Sha224, Sha256, Sha384, Sha512,
+};
+
+#[derive(Clone, Copy)]
+struct KnownAnswer {
+    input: &'static [u8],
+    sha224: [u8; SHA2_224_OUTPUT_LENGTH],
+    sha256: [u8; SHA2_256_OUTPUT_LENGTH],
+    sha384: [u8; SHA2_384_OUTPUT_LENGTH],
+    sha512: [u8; SHA2_512_OUTPUT_LENGTH],
+}
+
+/// Known answers taken from the FIPS 180-2 examples, extended with a message
+/// that leaves no space for the length field in its last block.
+const KNOWN_ANSWERS: &[KnownAnswer] = &[
+    KnownAnswer {
+        input: b"",
+        sha224: *b"\xD1\x4A\x02\x8C\x2A\x3A\x2B\xC9\x47\x61\x02\xBB\x28\x82\x34\xC4\x15\xA2\xB0\x1F\x82\x8E\xA6\x2A\xC5\xB3\xE4\x2F",
+        sha256: *b"\xE3\xB0\xC4\x42\x98\xFC\x1C\x14\x9A\xFB\xF4\xC8\x99\x6F\xB9\x24\x27\xAE\x41\xE4\x64\x9B\x93\x4C\xA4\x95\x99\x1B\x78\x52\xB8\x55",
+        sha384: *b"\x38\xB0\x60\xA7\x51\xAC\x96\x38\x4C\xD9\x32\x7E\xB1\xB1\xE3\x6A\x21\xFD\xB7\x11\x14\xBE\x07\x43\x4C\x0C\xC7\xBF\x63\xF6\xE1\xDA\x27\x4E\xDE\xBF\xE7\x6F\x65\xFB\xD5\x1A\xD2\xF1\x48\x98\xB9\x5B",
+        sha512: *b"\xCF\x83\xE1\x35\x7E\xEF\xB8\xBD\xF1\x54\x28\x50\xD6\x6D\x80\x07\xD6\x20\xE4\x05\x0B\x57\x15\xDC\x83\xF4\xA9\x21\xD3\x6C\xE9\xCE\x47\xD0\xD1\x3C\x5D\x85\xF2\xB0\xFF\x83\x18\xD2\x87\x7E\xEC\x2F\x63\xB9\x31\xBD\x47\x41\x7A\x81\xA5\x38\x32\x7A\xF9\x27\xDA\x3E",
+    },
+    KnownAnswer {
+        input: b"abc",
+        sha224: *b"\x23\x09\x7D\x22\x34\x05\xD8\x22\x86\x42\xA4\x77\xBD\xA2\x55\xB3\x2A\xAD\xBC\xE4\xBD\xA0\xB3\xF7\xE3\x6C\x9D\xA7",
+        sha256: *b"\xBA\x78\x16\xBF\x8F\x01\xCF\xEA\x41\x41\x40\xDE\x5D\xAE\x22\x23\xB0\x03\x61\xA3\x96\x17\x7A\x9C\xB4\x10\xFF\x61\xF2\x00\x15\xAD",
+        sha384: *b"\xCB\x00\x75\x3F\x45\xA3\x5E\x8B\xB5\xA0\x3D\x69\x9A\xC6\x50\x07\x27\x2C\x32\xAB\x0E\xDE\xD1\x63\x1A\x8B\x60\x5A\x43\xFF\x5B\xED\x80\x86\x07\x2B\xA1\xE7\xCC\x23\x58\xBA\xEC\xA1\x34\xC8\x25\xA7",
+        sha512: *b"\xDD\xAF\x35\xA1\x93\x61\x7A\xBA\xCC\x41\x73\x49\xAE\x20\x41\x31\x12\xE6\xFA\x4E\x89\xA9\x7E\xA2\x0A\x9E\xEE\xE6\x4B\x55\xD3\x9A\x21\x92\x99\x2A\x27\x4F\xC1\xA8\x36\xBA\x3C\x23\xA3\xFE\xEB\xBD\x45\x4D\x44\x23\x64\x3C\xE8\x0E\x2A\x9A\xC9\x4F\xA5\x4C\xA4\x9F",
+    },
+    KnownAnswer {
+        input: b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+        sha224: *b"\x75\x38\x8B\x16\x51\x27\x76\xCC\x5D\xBA\x5D\xA1\xFD\x89\x01\x50\xB0\xC6\x45\x5C\xB4\xF5\x8B\x19\x52\x52\x25\x25",
+        sha256: *b"\x24\x8D\x6A\x61\xD2\x06\x38\xB8\xE5\xC0\x26\x93\x0C\x3E\x60\x39\xA3\x3C\xE4\x59\x64\xFF\x21\x67\xF6\xEC\xED\xD4\x19\xDB\x06\xC1",
+        sha384: *b"\x33\x91\xFD\xDD\xFC\x8D\xC7\x39\x37\x07\xA6\x5B\x1B\x47\x09\x39\x7C\xF8\xB1\xD1\x62\xAF\x05\xAB\xFE\x8F\x45\x0D\xE5\xF3\x6B\xC6\xB0\x45\x5A\x85\x20\xBC\x4E\x6F\x5F\xE9\x5B\x1F\xE3\xC8\x45\x2B",
+        sha512: *b"\x20\x4A\x8F\xC6\xDD\xA8\x2F\x0A\x0C\xED\x7B\xEB\x8E\x08\xA4\x16\x57\xC1\x6E\xF4\x68\xB2\x28\xA8\x27\x9B\xE3\x31\xA7\x03\xC3\x35\x96\xFD\x15\xC1\x3B\x1B\x07\xF9\xAA\x1D\x3B\xEA\x57\x78\x9C\xA0\x31\xAD\x85\xC7\xA7\x1D\xD7\x03\x54\xEC\x63\x12\x38\xCA\x34\x45",
+    },
+    KnownAnswer {
+        input: b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu",
+        sha224: *b"\xC9\x7C\xA9\xA5\x59\x85\x0C\xE9\x7A\x04\xA9\x6D\xEF\x6D\x99\xA9\xE0\xE0\xE2\xAB\x14\xE6\xB8\xDF\x26\x5F\xC0\xB3",
+        sha256: *b"\xCF\x5B\x16\xA7\x78\xAF\x83\x80\x03\x6C\xE5\x9E\x7B\x04\x92\x37\x0B\x24\x9B\x11\xE8\xF0\x7A\x51\xAF\xAC\x45\x03\x7A\xFE\xE9\xD1",
+        sha384: *b"\x09\x33\x0C\x33\xF7\x11\x47\xE8\x3D\x19\x2F\xC7\x82\xCD\x1B\x47\x53\x11\x1B\x17\x3B\x3B\x05\xD2\x2F\xA0\x80\x86\xE3\xB0\xF7\x12\xFC\xC7\xC7\x1A\x55\x7E\x2D\xB9\x66\xC3\xE9\xFA\x91\x74\x60\x39",
+        sha512: *b"\x8E\x95\x9B\x75\xDA\xE3\x13\xDA\x8C\xF4\xF7\x28\x14\xFC\x14\x3F\x8F\x77\x79\xC6\xEB\x9F\x7F\xA1\x72\x99\xAE\xAD\xB6\x88\x90\x18\x50\x1D\x28\x9E\x49\x00\xF7\xE4\x33\x1B\x99\xDE\xC4\xB5\x43\x3A\xC7\xD3\x29\xEE\xB6\xDD\x26\x54\x5E\x96\xE5\x5B\x87\x4B\xE9\x09",
+    },
+    KnownAnswer {
+        input: b"0123456789ABCDEF0123456789ABCDEF0123456789ABCDEF0123456789ABCDE",
+        sha224: *b"\x06\x55\x70\x60\x0C\x47\x91\x40\x49\xF9\x10\x52\x3E\xD2\x49\x7F\x62\xDF\xBA\x32\x68\x67\xB9\x29\x34\x81\xDD\x96",
+        sha256: *b"\x3A\x96\x8B\x93\xD1\xDD\xA5\x59\xD3\x2F\x84\x44\xAF\x5E\x98\xC4\x87\x10\x0B\x03\xB9\xDF\xF1\x4B\xC1\x30\xBE\x94\xBC\x33\xC2\xAF",
+        sha384: *b"\xE8\xD0\x4B\x05\xDE\x1B\x89\x97\xD1\x2E\xFB\xA9\x2B\x4C\x9F\xAB\x43\xFC\x37\xD1\x83\xC2\x4E\x09\x21\x26\x66\x9D\xAC\x1B\x79\xB9\xEC\xEC\x00\x07\x00\x5E\xC5\x12\xBA\xA0\x0A\x01\x0D\xDE\xAC\x47",
+        sha512: *b"\x0D\x54\xD7\x4D\x08\x43\x7B\x48\x80\xEC\x16\x32\xC4\xFD\xB9\xE7\xF1\x8B\xCC\x7B\x66\xB6\xD5\x29\x80\x86\x42\x78\x39\xD4\x90\x88\x12\xF0\x01\x94\x39\x38\xB0\x97\x3C\x10\x61\xD2\x3B\x39\x0D\x4C\xDB\x6A\xE9\x9E\x03\x39\xCF\xAA\x97\x10\xE2\x4E\x67\x33\x86\x45",
+    },
+];
+
+/// Outcome of the known-answer tests of a single algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KnownAnswerTestOutcome {
+    algorithm: Algorithm,
+    failed_vector: Option<usize>,
+}
+
+impl KnownAnswerTestOutcome {
+    #[must_use]
+    pub const fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// Returns the index of the first known answer that did not match, if any.
+    #[must_use]
+    pub const fn failed_vector(&self) -> Option<usize> {
+        self.failed_vector
+    }
+
+    #[must_use]
+    pub const fn is_passed(&self) -> bool {
+        self.failed_vector.is_none()
+    }
+}
+
+/// Report returned by [`self_test`], holding one outcome per algorithm.
+#[must_use]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SelfTestReport {
+    outcomes: [KnownAnswerTestOutcome; Algorithm::ALL.len()],
+}
+
+impl SelfTestReport {
+    #[must_use]
+    pub const fn outcomes(&self) -> &[KnownAnswerTestOutcome; Algorithm::ALL.len()] {
+        &self.outcomes
+    }
+
+    /// Returns `true` when every algorithm produced all of the known answers.
+    #[must_use]
+    pub const fn is_passed(&self) -> bool {
+        let mut index = 0;
+
+        while index < self.outcomes.len() {
+            if !self.outcomes[index].is_passed() {
+                return false;
+            }
+
+            index += 1;
+        }
+
+        true
+    }
+
+    /// Returns the outcomes of the algorithms which failed the known-answer tests.
+    pub fn failures(&self) -> impl Iterator<Item = &KnownAnswerTestOutcome> {
+        self.outcomes.iter().filter(|outcome| !outcome.is_passed())
+    }
+}
+
+/// Runs the known-answer tests of every algorithm at runtime.
+///
+/// The known answers are hidden from the optimizer, so the digests are computed
+/// when this function is called rather than folded while compiling.
+pub fn self_test() -> SelfTestReport {
+    run(black_box(KNOWN_ANSWERS))
+}
+
+const fn run(known_answers: &[KnownAnswer]) -> SelfTestReport {
+    SelfTestReport {
+        outcomes: [
+            KnownAnswerTestOutcome {
+                algorithm: Algorithm::Sha224,
+                failed_vector: sha224_first_failure(known_answers),
+            },
+            KnownAnswerTestOutcome {
+                algorithm: Algorithm::Sha256,
+                failed_vector: sha256_first_failure(known_answers),
+            },
+            KnownAnswerTestOutcome {
+                algorithm: Algorithm::Sha384,
+                failed_vector: sha384_first_failure(known_answers),
+            },
+            KnownAnswerTestOutcome {
+                algorithm: Algorithm::Sha512,
+                failed_vector: sha512_first_failure(known_answers),
+            },
+        ],
+    }
+}
+
+const fn bytes_eq(left: &[u8], right: &[u8]) -> bool {
+    if left.len() != right.len() {
+        return false;
+    }
+
+    let mut index = 0;
+
+    while index < left.len() {
+        if left[index] != right[index] {
+            return false;
+        }
+
+        index += 1;
+    }
+
+    true
+}
+
+macro_rules! first_failure {
+    ($($fn: ident: $sha_type: ident => $field: ident),+ $(,)?) => {
+        $(
+            const fn $fn(known_answers: &[KnownAnswer]) -> Option<usize> {
+                let mut index = 0;
+
+                while index < known_answers.len() {
+                    let passed = match $sha_type::new().update(known_answers[index].input) {
+                        Ok(sha) => bytes_eq(&sha.finalize(), &known_answers[index].$field),
+                        Err(_) => false,
+                    };
+
+                    if !passed {
+                        return Some(index);
+                    }
+
+                    index += 1;
+                }
+
+                None
+            }
+        )+
+    };
+}
+
+first_failure![
+    sha224_first_failure: Sha224 => sha224,
+    sha256_first_failure: Sha256 => sha256,
+    sha384_first_failure: Sha384 => sha384,
+    sha512_first_failure: Sha512 => sha512,
+];
+
+const _: () = assert!(
+    sha224_first_failure(KNOWN_ANSWERS).is_none(),
+    "SHA-224 failed its known-answer tests at compile time!",
+);
+
+const _: () = assert!(
+    sha256_first_failure(KNOWN_ANSWERS).is_none(),
+    "SHA-256 failed its known-answer tests at compile time!",
+);
+
+const _: () = assert!(
+    sha384_first_failure(KNOWN_ANSWERS).is_none(),
+    "SHA-384 failed its known-answer tests at compile time!",
+);
+
+const _: () = assert!(
+    sha512_first_failure(KNOWN_ANSWERS).is_none(),
+    "SHA-512 failed its known-answer tests at compile time!",
+);
+
+#[cfg(test)]
+mod tests {
+    use super::{run, KNOWN_ANSWERS};
+    use crate::algorithm::Algorithm;
+
+    #[test]
+    fn test_corrupted_known_answer_fails() {
+        let mut known_answers = [KNOWN_ANSWERS[0], KNOWN_ANSWERS[1], KNOWN_ANSWERS[2]];
+
+        known_answers[1].sha384[0] ^= 1;
+
+        let report = run(&known_answers);
+
+        assert!(!report.is_passed());
+
+        let mut failures = report.failures();
+
+        let failure = failures.next().unwrap();
+
+        assert_eq!(failure.algorithm(), Algorithm::Sha384);
+        assert_eq!(failure.failed_vector(), Some(1));
+        assert!(!failure.is_passed());
+
+        assert!(failures.next().is_none());
+    }
+}
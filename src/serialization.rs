@@ -0,0 +1,224 @@
+//! Serde support for digests and exported states.
+//!
+//! Human-readable formats, e.g. JSON, represent them as lowercase hexadecimal
+//! strings, while the other formats, e.g. CBOR, represent them as byte
+//! strings. Deserializing rejects any length but the expected one.
+//!
+//! [`AnyDigest`] is told apart by the length of the digest, which differs for
+//! every algorithm. The digests returned by the hashers are plain arrays, which
+//! are serialized the same way with the [`digest`] module:
+//!
+//! ```
+//! use compile_time_sha2::Sha256;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Upload {
+//!     #[serde(with = "compile_time_sha2::serialization::digest")]
+//!     sha256: [u8; 32],
+//! }
+//!
+//! let upload = Upload {
+//!     sha256: Sha256::digest(b"abc").unwrap(),
+//! };
+//!
+//! assert_eq!(
+//!     serde_json::to_string(&upload).unwrap(),
+//!     r#"{"sha256":"ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"}"#,
+//! );
+//! ```
+
+use core::fmt::{Formatter, Result as FmtResult};
+
+use serde::{
+    de::{Error as _, Expected, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{
+    any::AnyDigest,
+    consts::{
+        SHA2_224_OUTPUT_LENGTH, SHA2_256_OUTPUT_LENGTH, SHA2_384_OUTPUT_LENGTH,
+        SHA2_512_OUTPUT_LENGTH,
+    },
+    encoding::{decode_hex_into, Hex},
+    state::{ExportedState, MAX_EXPORTED_STATE_LENGTH},
+};
+
+/// Serializes and deserializes digests stored as arrays, to be used with
+/// `#[serde(with = "compile_time_sha2::serialization::digest")]`.
+pub mod digest {
+    use serde::{de::Error as _, Deserializer, Serializer};
+
+    use super::{deserialize_bytes, serialize_bytes, ExpectedLength};
+
+    /// Serializes the digest as lowercase hexadecimal digits or as bytes,
+    /// depending on the format.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the serializer fails.
+    pub fn serialize<S, const N: usize>(digest: &[u8; N], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(digest, serializer)
+    }
+
+    /// Deserializes a digest of exactly `N` bytes.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the input is neither valid
+    /// hexadecimal nor bytes, or if its length is not `N` bytes.
+    pub fn deserialize<'de, D, const N: usize>(deserializer: D) -> Result<[u8; N], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (bytes, length) = deserialize_bytes(deserializer)?;
+
+        if length == N {
+            let mut digest = [0; N];
+
+            digest.copy_from_slice(&bytes[..N]);
+
+            Ok(digest)
+        } else {
+            Err(D::Error::invalid_length(length, &ExpectedLength(N)))
+        }
+    }
+}
+
+impl Serialize for AnyDigest {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self.as_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for AnyDigest {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        fn copy<const N: usize>(bytes: &[u8]) -> [u8; N] {
+            let mut digest = [0; N];
+
+            digest.copy_from_slice(&bytes[..N]);
+
+            digest
+        }
+
+        let (bytes, length) = deserialize_bytes(deserializer)?;
+
+        match length {
+            SHA2_224_OUTPUT_LENGTH => Ok(Self::Sha224(copy(&bytes))),
+            SHA2_256_OUTPUT_LENGTH => Ok(Self::Sha256(copy(&bytes))),
+            SHA2_384_OUTPUT_LENGTH => Ok(Self::Sha384(copy(&bytes))),
+            SHA2_512_OUTPUT_LENGTH => Ok(Self::Sha512(copy(&bytes))),
+            _ => Err(D::Error::invalid_length(
+                length,
+                &"a digest of 28, 32, 48 or 64 bytes",
+            )),
+        }
+    }
+}
+
+impl Serialize for ExportedState {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize_bytes(self.as_bytes(), serializer)
+    }
+}
+
+/// Checks the exported state as done by [`ExportedState::from_bytes`].
+impl<'de> Deserialize<'de> for ExportedState {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (bytes, length) = deserialize_bytes(deserializer)?;
+
+        Self::from_bytes(&bytes[..length]).map_err(D::Error::custom)
+    }
+}
+
+fn serialize_bytes<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        serializer.collect_str(&Hex(bytes))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+/// Deserializes at most [`MAX_EXPORTED_STATE_LENGTH`] bytes, returning them
+/// along with their length.
+fn deserialize_bytes<'de, D>(
+    deserializer: D,
+) -> Result<([u8; MAX_EXPORTED_STATE_LENGTH], usize), D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(BytesVisitor)
+    } else {
+        deserializer.deserialize_bytes(BytesVisitor)
+    }
+}
+
+struct BytesVisitor;
+
+impl Visitor<'_> for BytesVisitor {
+    type Value = ([u8; MAX_EXPORTED_STATE_LENGTH], usize);
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str("hexadecimal digits or bytes")
+    }
+
+    fn visit_str<E>(self, text: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        let length = text.len() / 2;
+
+        if text.len() % 2 != 0 || length > MAX_EXPORTED_STATE_LENGTH {
+            return Err(E::invalid_length(text.len(), &self));
+        }
+
+        let mut bytes = [0; MAX_EXPORTED_STATE_LENGTH];
+
+        decode_hex_into(text, &mut bytes[..length]).map_err(E::custom)?;
+
+        Ok((bytes, length))
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        if value.len() > MAX_EXPORTED_STATE_LENGTH {
+            return Err(E::invalid_length(value.len(), &self));
+        }
+
+        let mut bytes = [0; MAX_EXPORTED_STATE_LENGTH];
+
+        bytes[..value.len()].copy_from_slice(value);
+
+        Ok((bytes, value.len()))
+    }
+}
+
+struct ExpectedLength(usize);
+
+impl Expected for ExpectedLength {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "a digest of {} bytes", self.0)
+    }
+}
@@ -0,0 +1,258 @@
+//! Export and import of the running state of the hashers, e.g. to store the
+//! hash of a partially received upload and resume it later.
+//!
+//! ```
+//! use compile_time_sha2::{state::ExportedState, Sha256};
+//!
+//! let sha = Sha256::new().update(b"first part, ").unwrap();
+//!
+//! let stored = sha.export_state().as_bytes().to_vec();
+//!
+//! let state = ExportedState::from_bytes(&stored).unwrap();
+//!
+//! let sha = Sha256::import_state(&state).unwrap();
+//!
+//! assert_eq!(
+//!     sha.update(b"second part").unwrap().finalize(),
+//!     Sha256::digest(b"first part, second part").unwrap(),
+//! );
+//! ```
+//!
+//! # Format
+//!
+//! The exported bytes are, in order:
+//!
+//! - the tag of the algorithm, which is the last arc of its object identifier,
+//!   e.g. `1` for SHA-256;
+//! - the length in bytes of the message so far, as a big-endian `u128`;
+//! - the eight words of the state, each in big-endian order;
+//! - the bytes of the message following the last processed block.
+//!
+//! The buffered bytes are part of the message, thus an exported state should
+//! be kept as confidential as the message itself.
+
+use core::{
+    fmt::{Debug, Formatter, Result as FmtResult},
+    mem::size_of,
+};
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+use crate::{algorithm::Algorithm, error::StateImportError, internals::slice::split_at};
+
+/// Length of the tag of the algorithm and of the message length.
+const HEADER_LENGTH: usize = 1 + size_of::<u128>();
+
+/// Maximum length of an exported state, reached by SHA-384 and SHA-512 with a
+/// block lacking a single byte.
+pub const MAX_EXPORTED_STATE_LENGTH: usize =
+    HEADER_LENGTH + state_length(Algorithm::Sha512) + Algorithm::Sha512.block_size() - 1;
+
+/// Running state of a hasher, as returned by `export_state`.
+#[must_use]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ExportedState {
+    bytes: [u8; MAX_EXPORTED_STATE_LENGTH],
+    length: usize,
+}
+
+impl ExportedState {
+    /// Parses an exported state, checking that its length matches the length
+    /// of the message it encodes.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the tag does not identify an
+    /// algorithm, if the length of the bytes is invalid, or if the message
+    /// length reaches the limit of the algorithm.
+    pub const fn from_bytes(bytes: &[u8]) -> Result<Self, StateImportError> {
+        let Some(&tag) = bytes.first() else {
+            return Err(StateImportError::InvalidLength {
+                expected: HEADER_LENGTH + state_length(Algorithm::Sha256),
+                actual: 0,
+            });
+        };
+
+        let Some(algorithm) = algorithm_from_tag(tag) else {
+            return Err(StateImportError::UnknownAlgorithm { tag });
+        };
+
+        let fixed_length = HEADER_LENGTH + state_length(algorithm);
+
+        if bytes.len() < fixed_length {
+            return Err(StateImportError::InvalidLength {
+                expected: fixed_length,
+                actual: bytes.len(),
+            });
+        }
+
+        let mut message_length = [0; size_of::<u128>()];
+
+        let mut index = 0;
+
+        while index < message_length.len() {
+            message_length[index] = bytes[1 + index];
+
+            index += 1;
+        }
+
+        let message_length = u128::from_be_bytes(message_length);
+
+        if message_length >= message_length_limit(algorithm) {
+            return Err(StateImportError::MessageLengthOutOfRange);
+        }
+
+        // Block sizes divide 256, thus the lowest byte of the length is enough.
+        let expected =
+            fixed_length + message_length.to_le_bytes()[0] as usize % algorithm.block_size();
+
+        if bytes.len() == expected {
+            Ok(Self::new(algorithm, message_length).push(split_at(bytes, HEADER_LENGTH).1))
+        } else {
+            Err(StateImportError::InvalidLength {
+                expected,
+                actual: bytes.len(),
+            })
+        }
+    }
+
+    #[must_use]
+    pub const fn algorithm(&self) -> Algorithm {
+        match algorithm_from_tag(self.bytes[0]) {
+            Some(algorithm) => algorithm,
+            None => unreachable!(),
+        }
+    }
+
+    /// Returns the length in bytes of the message hashed so far.
+    #[must_use]
+    pub const fn message_length(&self) -> u128 {
+        u128::from_be_bytes(self.read(1))
+    }
+
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8] {
+        split_at(&self.bytes, self.length).0
+    }
+
+    /// Starts an exported state with its header, to be followed by the state
+    /// and the buffered bytes.
+    pub(crate) const fn new(algorithm: Algorithm, message_length: u128) -> Self {
+        let mut exported = Self {
+            bytes: [0; MAX_EXPORTED_STATE_LENGTH],
+            length: 0,
+        };
+
+        exported = exported.push(&[algorithm_tag(algorithm)]);
+
+        exported.push(&message_length.to_be_bytes())
+    }
+
+    pub(crate) const fn push(mut self, bytes: &[u8]) -> Self {
+        let mut index = 0;
+
+        while index < bytes.len() {
+            self.bytes[self.length] = bytes[index];
+
+            self.length += 1;
+
+            index += 1;
+        }
+
+        self
+    }
+
+    /// Returns the `N` bytes of the state starting at `offset`, counted from
+    /// the start of the state words.
+    pub(crate) const fn state_bytes<const N: usize>(&self, offset: usize) -> [u8; N] {
+        self.read(HEADER_LENGTH + offset)
+    }
+
+    /// Returns the bytes of the message following the last processed block.
+    pub(crate) const fn buffered(&self) -> &[u8] {
+        split_at(
+            self.as_bytes(),
+            HEADER_LENGTH + state_length(self.algorithm()),
+        )
+        .1
+    }
+
+    const fn read<const N: usize>(&self, offset: usize) -> [u8; N] {
+        let mut output = [0; N];
+
+        let mut index = 0;
+
+        while index < N {
+            output[index] = self.bytes[offset + index];
+
+            index += 1;
+        }
+
+        output
+    }
+}
+
+impl AsRef<[u8]> for ExportedState {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+/// Only shows the algorithm and the length of the message, leaving out the
+/// buffered message and the state.
+impl Debug for ExportedState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ExportedState")
+            .field("algorithm", &self.algorithm())
+            .field("message_length", &self.message_length())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+/// Overwrites the state and the buffered message with zeroes, keeping the
+/// algorithm so that the exported state stays valid.
+impl Zeroize for ExportedState {
+    fn zeroize(&mut self) {
+        let algorithm = self.algorithm();
+
+        self.bytes.zeroize();
+
+        self.bytes[0] = algorithm_tag(algorithm);
+
+        self.length = HEADER_LENGTH + state_length(algorithm);
+    }
+}
+
+const fn algorithm_tag(algorithm: Algorithm) -> u8 {
+    match algorithm {
+        Algorithm::Sha256 => 1,
+        Algorithm::Sha384 => 2,
+        Algorithm::Sha512 => 3,
+        Algorithm::Sha224 => 4,
+    }
+}
+
+const fn algorithm_from_tag(tag: u8) -> Option<Algorithm> {
+    match tag {
+        1 => Some(Algorithm::Sha256),
+        2 => Some(Algorithm::Sha384),
+        3 => Some(Algorithm::Sha512),
+        4 => Some(Algorithm::Sha224),
+        _ => None,
+    }
+}
+
+/// Returns the length of the eight state words, i.e. half of a block.
+const fn state_length(algorithm: Algorithm) -> usize {
+    algorithm.block_size() / 2
+}
+
+/// Returns the exclusive upper bound of the message length in bytes.
+const fn message_length_limit(algorithm: Algorithm) -> u128 {
+    match algorithm {
+        Algorithm::Sha224 | Algorithm::Sha256 => 1 << 61,
+        Algorithm::Sha384 | Algorithm::Sha512 => 1 << 125,
+    }
+}
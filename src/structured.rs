@@ -0,0 +1,422 @@
+//! Unambiguous hashing of structured values.
+//!
+//! Concatenating the fields of a value makes different values collide, e.g.
+//! `("ab", "c")` and `("a", "bc")`. The [`Sha2Hash`] trait instead feeds an
+//! encoding from which the value could be decoded back, given its type.
+//!
+//! # Encoding
+//!
+//! Version [`ENCODING_VERSION`] of the encoding, prepended as a single byte by
+//! [`fingerprint`], is defined as follows:
+//!
+//! - integers are written in big-endian order with their own width, except for
+//!   `usize` and `isize`, which are widened to 64 bits;
+//! - [`bool`] is written as the byte `0` or `1`, and [`char`] as a `u32`;
+//! - [`str`], slices and, with the `std` feature, `String` and `Vec` are
+//!   written as their length, encoded as a `usize`, followed by their
+//!   elements;
+//! - arrays, tuples and structs are written as their elements in order, as
+//!   their length is given by the type;
+//! - [`Option`] is written as the byte `0` for `None`, or the byte `1` followed
+//!   by the value;
+//! - enums deriving [`Sha2Hash`] are written as the index of the variant,
+//!   encoded as a `u32`, followed by its fields;
+//! - with the `std` feature, maps and sets are written as their length
+//!   followed by their entries, sorted by key, each map entry being written as
+//!   the key followed by the value.
+//!
+//! # Compile time
+//!
+//! Trait methods cannot be called while compiling, thus the encodings of the
+//! primitives are provided by `const fn`s as well, e.g. [`encode_length`].
+//! Together with the `to_be_bytes` methods of the integers, they allow
+//! fingerprinting fixed values with `digest_parts`:
+//!
+//! ```
+//! use compile_time_sha2::{
+//!     structured::{encode_bool, encode_length, fingerprint, ENCODING_VERSION},
+//!     Sha256,
+//! };
+//!
+//! const FINGERPRINT: [u8; 32] = match Sha256::digest_parts(&[
+//!     &[ENCODING_VERSION],
+//!     &8080_u16.to_be_bytes(),
+//!     &encode_length("api".len()),
+//!     b"api",
+//!     &encode_bool(true),
+//! ]) {
+//!     Ok(digest) => digest,
+//!     Err(_) => panic!("message too long"),
+//! };
+//!
+//! assert_eq!(
+//!     fingerprint::<Sha256, _>(&(8080_u16, "api", true)),
+//!     Ok(FINGERPRINT),
+//! );
+//! ```
+
+#[cfg(feature = "std")]
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    hash::BuildHasher,
+};
+
+use crate::{error::MessageTooLong, Sha2Algorithm};
+
+/// Version of the encoding described in the [module documentation](self).
+pub const ENCODING_VERSION: u8 = 1;
+
+/// Derives [`Sha2Hash`], hashing the fields in declaration order and prefixing
+/// enums with the index of the variant.
+#[cfg(feature = "derive")]
+pub use compile_time_sha2_derive::Sha2Hash;
+
+/// Value with an unambiguous encoding, fed to the hashers.
+///
+/// Implementations must write an encoding from which the value could be
+/// decoded back, e.g. by writing the fields of a struct with their own
+/// implementations, as done by `#[derive(Sha2Hash)]`.
+pub trait Sha2Hash {
+    /// Updates the hasher with the encoding of the value.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the total length of the message
+    /// exceeds the limit of the algorithm.
+    fn sha2_hash<H>(&self, sha: H) -> Result<H, MessageTooLong>
+    where
+        H: Sha2Algorithm;
+
+    /// Updates the hasher with the encodings of the elements, without the
+    /// length of the slice.
+    ///
+    /// Overridden for `u8`, which writes the bytes at once.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the total length of the message
+    /// exceeds the limit of the algorithm.
+    fn sha2_hash_slice<H>(slice: &[Self], mut sha: H) -> Result<H, MessageTooLong>
+    where
+        Self: Sized,
+        H: Sha2Algorithm,
+    {
+        for element in slice {
+            sha = element.sha2_hash(sha)?;
+        }
+
+        Ok(sha)
+    }
+}
+
+/// Hashes the value with the given algorithm, prefixed with
+/// [`ENCODING_VERSION`].
+///
+/// # Errors
+///
+/// This function will return an error if the encoding exceeds the length limit
+/// of the algorithm.
+pub fn fingerprint<H, T>(value: &T) -> Result<H::Output, MessageTooLong>
+where
+    H: Sha2Algorithm,
+    T: Sha2Hash + ?Sized,
+{
+    value
+        .sha2_hash(H::new().update(&[ENCODING_VERSION])?)
+        .map(H::finalize)
+}
+
+/// Encodes a length, e.g. of a slice, as a `usize`.
+#[must_use]
+pub const fn encode_length(length: usize) -> [u8; 8] {
+    encode_usize(length)
+}
+
+#[must_use]
+pub const fn encode_usize(value: usize) -> [u8; 8] {
+    (value as u64).to_be_bytes()
+}
+
+#[must_use]
+pub const fn encode_isize(value: isize) -> [u8; 8] {
+    (value as i64).to_be_bytes()
+}
+
+#[must_use]
+pub const fn encode_bool(value: bool) -> [u8; 1] {
+    [value as u8]
+}
+
+#[must_use]
+pub const fn encode_char(value: char) -> [u8; 4] {
+    (value as u32).to_be_bytes()
+}
+
+macro_rules! impl_for_integers {
+    ($($type: ty),+ $(,)?) => {
+        $(
+            impl Sha2Hash for $type {
+                fn sha2_hash<H>(&self, sha: H) -> Result<H, MessageTooLong>
+                where
+                    H: Sha2Algorithm,
+                {
+                    sha.update(&self.to_be_bytes())
+                }
+            }
+        )+
+    };
+}
+
+impl_for_integers![u16, u32, u64, u128, i8, i16, i32, i64, i128];
+
+macro_rules! impl_with_encoder {
+    ($($type: ty: $encode: ident),+ $(,)?) => {
+        $(
+            impl Sha2Hash for $type {
+                fn sha2_hash<H>(&self, sha: H) -> Result<H, MessageTooLong>
+                where
+                    H: Sha2Algorithm,
+                {
+                    sha.update(&$encode(*self))
+                }
+            }
+        )+
+    };
+}
+
+impl_with_encoder![
+    usize: encode_usize,
+    isize: encode_isize,
+    bool: encode_bool,
+    char: encode_char,
+];
+
+impl Sha2Hash for u8 {
+    fn sha2_hash<H>(&self, sha: H) -> Result<H, MessageTooLong>
+    where
+        H: Sha2Algorithm,
+    {
+        sha.update(&[*self])
+    }
+
+    fn sha2_hash_slice<H>(slice: &[Self], sha: H) -> Result<H, MessageTooLong>
+    where
+        H: Sha2Algorithm,
+    {
+        sha.update(slice)
+    }
+}
+
+impl Sha2Hash for str {
+    fn sha2_hash<H>(&self, sha: H) -> Result<H, MessageTooLong>
+    where
+        H: Sha2Algorithm,
+    {
+        self.as_bytes().sha2_hash(sha)
+    }
+}
+
+impl<T> Sha2Hash for [T]
+where
+    T: Sha2Hash,
+{
+    fn sha2_hash<H>(&self, sha: H) -> Result<H, MessageTooLong>
+    where
+        H: Sha2Algorithm,
+    {
+        T::sha2_hash_slice(self, self.len().sha2_hash(sha)?)
+    }
+}
+
+impl<T, const N: usize> Sha2Hash for [T; N]
+where
+    T: Sha2Hash,
+{
+    fn sha2_hash<H>(&self, sha: H) -> Result<H, MessageTooLong>
+    where
+        H: Sha2Algorithm,
+    {
+        T::sha2_hash_slice(self, sha)
+    }
+}
+
+impl<T> Sha2Hash for Option<T>
+where
+    T: Sha2Hash,
+{
+    fn sha2_hash<H>(&self, sha: H) -> Result<H, MessageTooLong>
+    where
+        H: Sha2Algorithm,
+    {
+        match self {
+            None => false.sha2_hash(sha),
+            Some(value) => value.sha2_hash(true.sha2_hash(sha)?),
+        }
+    }
+}
+
+impl<T> Sha2Hash for &T
+where
+    T: Sha2Hash + ?Sized,
+{
+    fn sha2_hash<H>(&self, sha: H) -> Result<H, MessageTooLong>
+    where
+        H: Sha2Algorithm,
+    {
+        T::sha2_hash(self, sha)
+    }
+}
+
+macro_rules! impl_for_tuples {
+    ($(($($element: ident $index: tt),*)),+ $(,)?) => {
+        $(
+            impl<$($element),*> Sha2Hash for ($($element,)*)
+            where
+                $($element: Sha2Hash,)*
+            {
+                fn sha2_hash<H>(&self, sha: H) -> Result<H, MessageTooLong>
+                where
+                    H: Sha2Algorithm,
+                {
+                    $(
+                        let sha = self.$index.sha2_hash(sha)?;
+                    )*
+
+                    Ok(sha)
+                }
+            }
+        )+
+    };
+}
+
+impl_for_tuples![
+    (),
+    (A 0),
+    (A 0, B 1),
+    (A 0, B 1, C 2),
+    (A 0, B 1, C 2, D 3),
+    (A 0, B 1, C 2, D 3, E 4),
+    (A 0, B 1, C 2, D 3, E 4, F 5),
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6),
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, I 7),
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, I 7, J 8),
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, I 7, J 8, K 9),
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, I 7, J 8, K 9, L 10),
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, I 7, J 8, K 9, L 10, M 11),
+];
+
+#[cfg(feature = "std")]
+impl Sha2Hash for String {
+    fn sha2_hash<H>(&self, sha: H) -> Result<H, MessageTooLong>
+    where
+        H: Sha2Algorithm,
+    {
+        self.as_str().sha2_hash(sha)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Sha2Hash for Vec<T>
+where
+    T: Sha2Hash,
+{
+    fn sha2_hash<H>(&self, sha: H) -> Result<H, MessageTooLong>
+    where
+        H: Sha2Algorithm,
+    {
+        self.as_slice().sha2_hash(sha)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Sha2Hash for Box<T>
+where
+    T: Sha2Hash + ?Sized,
+{
+    fn sha2_hash<H>(&self, sha: H) -> Result<H, MessageTooLong>
+    where
+        H: Sha2Algorithm,
+    {
+        T::sha2_hash(self, sha)
+    }
+}
+
+/// Hashes the length followed by the entries, which are already sorted.
+#[cfg(feature = "std")]
+fn hash_sorted<T, H>(
+    length: usize,
+    entries: impl IntoIterator<Item = T>,
+    sha: H,
+) -> Result<H, MessageTooLong>
+where
+    T: Sha2Hash,
+    H: Sha2Algorithm,
+{
+    entries
+        .into_iter()
+        .try_fold(length.sha2_hash(sha)?, |sha, entry| entry.sha2_hash(sha))
+}
+
+#[cfg(feature = "std")]
+impl<K, V> Sha2Hash for BTreeMap<K, V>
+where
+    K: Sha2Hash,
+    V: Sha2Hash,
+{
+    fn sha2_hash<H>(&self, sha: H) -> Result<H, MessageTooLong>
+    where
+        H: Sha2Algorithm,
+    {
+        hash_sorted(self.len(), self, sha)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Sha2Hash for BTreeSet<T>
+where
+    T: Sha2Hash,
+{
+    fn sha2_hash<H>(&self, sha: H) -> Result<H, MessageTooLong>
+    where
+        H: Sha2Algorithm,
+    {
+        hash_sorted(self.len(), self, sha)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> Sha2Hash for HashMap<K, V, S>
+where
+    K: Sha2Hash + Ord,
+    V: Sha2Hash,
+    S: BuildHasher,
+{
+    fn sha2_hash<H>(&self, sha: H) -> Result<H, MessageTooLong>
+    where
+        H: Sha2Algorithm,
+    {
+        let mut entries = self.iter().collect::<Vec<_>>();
+
+        entries.sort_unstable_by_key(|(key, _)| *key);
+
+        hash_sorted(self.len(), entries, sha)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, S> Sha2Hash for HashSet<T, S>
+where
+    T: Sha2Hash + Ord,
+    S: BuildHasher,
+{
+    fn sha2_hash<H>(&self, sha: H) -> Result<H, MessageTooLong>
+    where
+        H: Sha2Algorithm,
+    {
+        let mut entries = self.iter().collect::<Vec<_>>();
+
+        entries.sort_unstable();
+
+        hash_sorted(self.len(), entries, sha)
+    }
+}
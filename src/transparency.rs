@@ -0,0 +1,482 @@
+//! Merkle trees of transparency logs, as specified by RFC 6962 and RFC 9162.
+//!
+//! Leaves are hashed as `SHA256(0x00 || leaf)` and nodes as
+//! `SHA256(0x01 || left || right)`, so that no leaf hashes like a node. A tree
+//! of `n` leaves splits them after the largest power of two lower than `n`.
+//!
+//! Proofs are verified without allocation. Under `std`, a [`MerkleTree`]
+//! appends leaves and generates proofs:
+//!
+//! ```
+//! # #[cfg(feature = "std")]
+//! # {
+//! use compile_time_sha2::transparency::{
+//!     leaf_hash, verify_consistency, verify_inclusion, MerkleTree,
+//! };
+//!
+//! let mut tree = MerkleTree::new();
+//!
+//! for leaf in [&b"alpha"[..], b"bravo", b"charlie"] {
+//!     tree.push(leaf).unwrap();
+//! }
+//!
+//! let old_root = tree.root();
+//!
+//! let index = tree.push(b"delta").unwrap();
+//!
+//! let proof = tree.inclusion_proof(index, tree.len()).unwrap();
+//!
+//! let leaf = leaf_hash(b"delta").unwrap();
+//!
+//! assert_eq!(verify_inclusion(&leaf, index, tree.len(), &proof, &tree.root()), Ok(()));
+//!
+//! let proof = tree.consistency_proof(3, 4).unwrap();
+//!
+//! assert_eq!(verify_consistency(3, 4, &old_root, &tree.root(), &proof), Ok(()));
+//! # }
+//! ```
+
+use crate::{
+    constant_time::ct_eq,
+    error::{MessageTooLong, ProofError},
+    internals::slice::split_at,
+    Sha256,
+};
+
+/// Root of the empty tree, `SHA256("")`.
+pub const EMPTY_ROOT: [u8; 32] = Sha256::digest_array(&[]);
+
+/// Returns `SHA256(0x00 || leaf)`.
+///
+/// # Errors
+///
+/// This function will return an error if the prefixed leaf exceeds the length
+/// limit of SHA-256.
+pub const fn leaf_hash(leaf: &[u8]) -> Result<[u8; 32], MessageTooLong> {
+    Sha256::digest_parts(&[&[0x00], leaf])
+}
+
+/// Returns `SHA256(0x01 || left || right)`.
+#[must_use]
+pub const fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut node = [0x01; 65];
+
+    let mut index = 0;
+
+    while index < 32 {
+        node[1 + index] = left[index];
+        node[33 + index] = right[index];
+
+        index += 1;
+    }
+
+    Sha256::digest_array(&node)
+}
+
+/// Returns the root of the tree of the leaves.
+///
+/// # Errors
+///
+/// This function will return an error if a prefixed leaf exceeds the length
+/// limit of SHA-256.
+pub fn root<T>(leaves: &[T]) -> Result<[u8; 32], MessageTooLong>
+where
+    T: AsRef<[u8]>,
+{
+    match leaves {
+        [] => Ok(EMPTY_ROOT),
+        [leaf] => leaf_hash(leaf.as_ref()),
+        _ => {
+            let (left, right) = leaves.split_at(split(leaves.len()));
+
+            Ok(node_hash(&root(left)?, &root(right)?))
+        }
+    }
+}
+
+/// Returns the root of the tree whose leaves have the given hashes.
+#[must_use]
+pub const fn root_from_leaf_hashes(leaf_hashes: &[[u8; 32]]) -> [u8; 32] {
+    match leaf_hashes {
+        [] => EMPTY_ROOT,
+        [leaf_hash] => *leaf_hash,
+        _ => {
+            let (left, right) = split_at(leaf_hashes, split(leaf_hashes.len()));
+
+            node_hash(&root_from_leaf_hashes(left), &root_from_leaf_hashes(right))
+        }
+    }
+}
+
+/// Returns the largest power of two lower than `size`, which is at least 2.
+const fn split(size: usize) -> usize {
+    1 << (usize::BITS - 1 - (size - 1).leading_zeros())
+}
+
+/// Verifies that the leaf hash is at `index` in the tree of `size` leaves
+/// with the given root, following RFC 9162, section 2.1.3.2.
+///
+/// # Errors
+///
+/// This function will return an error if the index is out of the tree, or if
+/// the proof has the wrong length or does not lead to the root.
+pub const fn verify_inclusion(
+    leaf_hash: &[u8; 32],
+    index: u64,
+    size: u64,
+    proof: &[[u8; 32]],
+    root: &[u8; 32],
+) -> Result<(), ProofError> {
+    if index >= size {
+        return Err(ProofError::IndexOutOfRange { index, size });
+    }
+
+    let (mut first, mut second) = (index, size - 1);
+
+    let mut node = *leaf_hash;
+
+    let mut position = 0;
+
+    while position < proof.len() {
+        if second == 0 {
+            return Err(ProofError::InvalidProofLength);
+        }
+
+        if first & 1 == 1 || first == second {
+            node = node_hash(&proof[position], &node);
+
+            while first & 1 == 0 && first != 0 {
+                first >>= 1;
+                second >>= 1;
+            }
+        } else {
+            node = node_hash(&node, &proof[position]);
+        }
+
+        first >>= 1;
+        second >>= 1;
+
+        position += 1;
+    }
+
+    if second != 0 {
+        Err(ProofError::InvalidProofLength)
+    } else if ct_eq(&node, root) {
+        Ok(())
+    } else {
+        Err(ProofError::RootMismatch)
+    }
+}
+
+/// Verifies that the tree of `old_size` leaves with `old_root` is a prefix of
+/// the tree of `new_size` leaves with `new_root`, following RFC 9162, section
+/// 2.1.4.2.
+///
+/// # Errors
+///
+/// This function will return an error if the old size exceeds the new one,
+/// or if the proof has the wrong length or does not lead to both roots.
+pub const fn verify_consistency(
+    old_size: u64,
+    new_size: u64,
+    old_root: &[u8; 32],
+    new_root: &[u8; 32],
+    proof: &[[u8; 32]],
+) -> Result<(), ProofError> {
+    if old_size > new_size {
+        return Err(ProofError::SizeOutOfRange {
+            size: old_size,
+            maximum: new_size,
+        });
+    }
+
+    // Trees of equal sizes, and the empty tree, need no proof.
+    if old_size == new_size || old_size == 0 {
+        return if !proof.is_empty() {
+            Err(ProofError::InvalidProofLength)
+        } else if (old_size == 0 && !ct_eq(old_root, &EMPTY_ROOT))
+            || (old_size != 0 && !ct_eq(old_root, new_root))
+        {
+            Err(ProofError::RootMismatch)
+        } else {
+            Ok(())
+        };
+    }
+
+    let Some(first_hash) = proof.first() else {
+        return Err(ProofError::InvalidProofLength);
+    };
+
+    // The old tree is a complete subtree of the new one when its size is a
+    // power of two, thus its root is omitted from the proof.
+    let (seed, mut position) = if old_size.is_power_of_two() {
+        (*old_root, 0)
+    } else {
+        (*first_hash, 1)
+    };
+
+    let (mut first, mut second) = (old_size - 1, new_size - 1);
+
+    while first & 1 == 1 {
+        first >>= 1;
+        second >>= 1;
+    }
+
+    let (mut old_node, mut new_node) = (seed, seed);
+
+    while position < proof.len() {
+        if second == 0 {
+            return Err(ProofError::InvalidProofLength);
+        }
+
+        if first & 1 == 1 || first == second {
+            old_node = node_hash(&proof[position], &old_node);
+            new_node = node_hash(&proof[position], &new_node);
+
+            while first & 1 == 0 && first != 0 {
+                first >>= 1;
+                second >>= 1;
+            }
+        } else {
+            new_node = node_hash(&new_node, &proof[position]);
+        }
+
+        first >>= 1;
+        second >>= 1;
+
+        position += 1;
+    }
+
+    if second != 0 {
+        Err(ProofError::InvalidProofLength)
+    } else if ct_eq(&old_node, old_root) && ct_eq(&new_node, new_root) {
+        Ok(())
+    } else {
+        Err(ProofError::RootMismatch)
+    }
+}
+
+/// Append-only tree keeping the roots of its complete subtrees, so that
+/// roots and proofs of any earlier size are computed with logarithmically
+/// many hashes.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MerkleTree {
+    /// Level `i` holds the roots of the complete subtrees of `2^i` leaves, the
+    /// first level holding the leaf hashes.
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+#[cfg(feature = "std")]
+impl MerkleTree {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a leaf, returning its index.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the prefixed leaf exceeds the
+    /// length limit of SHA-256.
+    pub fn push(&mut self, leaf: &[u8]) -> Result<u64, MessageTooLong> {
+        Ok(self.push_leaf_hash(leaf_hash(leaf)?))
+    }
+
+    /// Appends a leaf by its hash, returning its index.
+    pub fn push_leaf_hash(&mut self, leaf_hash: [u8; 32]) -> u64 {
+        let index = self.len();
+
+        let mut node = leaf_hash;
+
+        let mut level = 0;
+
+        loop {
+            if level == self.levels.len() {
+                self.levels.push(Vec::new());
+            }
+
+            let nodes = &mut self.levels[level];
+
+            nodes.push(node);
+
+            // A complete subtree is formed each time a level gets even.
+            if nodes.len() % 2 == 1 {
+                return index;
+            }
+
+            node = node_hash(&nodes[nodes.len() - 2], &node);
+
+            level += 1;
+        }
+    }
+
+    /// Returns the number of leaves.
+    #[must_use]
+    pub fn len(&self) -> u64 {
+        self.levels.first().map_or(0, |leaves| leaves.len() as u64)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[must_use]
+    pub fn leaf_hash(&self, index: u64) -> Option<[u8; 32]> {
+        let index = usize::try_from(index).ok()?;
+
+        self.levels.first()?.get(index).copied()
+    }
+
+    #[must_use]
+    pub fn root(&self) -> [u8; 32] {
+        self.subtree(0, self.levels.first().map_or(0, Vec::len))
+    }
+
+    /// Returns the root of the tree of the first `size` leaves.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the tree has fewer leaves.
+    pub fn root_at(&self, size: u64) -> Result<[u8; 32], ProofError> {
+        Ok(self.subtree(0, self.size(size)?))
+    }
+
+    /// Returns the proof that the leaf at `index` is in the tree of the first
+    /// `size` leaves.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the tree has fewer than `size`
+    /// leaves, or if the index is not lower than `size`.
+    pub fn inclusion_proof(&self, index: u64, size: u64) -> Result<Vec<[u8; 32]>, ProofError> {
+        let leaves = self.size(size)?;
+
+        let leaf = usize::try_from(index)
+            .ok()
+            .filter(|leaf| *leaf < leaves)
+            .ok_or(ProofError::IndexOutOfRange { index, size })?;
+
+        let mut proof = Vec::new();
+
+        self.path(leaf, 0, leaves, &mut proof);
+
+        Ok(proof)
+    }
+
+    /// Returns the proof that the tree of the first `old_size` leaves is a
+    /// prefix of the tree of the first `new_size` leaves, which is empty if
+    /// either size is zero or both are equal.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the tree has fewer than
+    /// `new_size` leaves, or if `old_size` exceeds `new_size`.
+    pub fn consistency_proof(
+        &self,
+        old_size: u64,
+        new_size: u64,
+    ) -> Result<Vec<[u8; 32]>, ProofError> {
+        let leaves = self.size(new_size)?;
+
+        let old_leaves = usize::try_from(old_size)
+            .ok()
+            .filter(|old_leaves| *old_leaves <= leaves)
+            .ok_or(ProofError::SizeOutOfRange {
+                size: old_size,
+                maximum: new_size,
+            })?;
+
+        let mut proof = Vec::new();
+
+        if old_leaves != 0 {
+            self.subproof(old_leaves, 0, leaves, true, &mut proof);
+        }
+
+        Ok(proof)
+    }
+
+    /// Checks that the tree has at least `size` leaves.
+    fn size(&self, size: u64) -> Result<usize, ProofError> {
+        let maximum = self.len();
+
+        match usize::try_from(size) {
+            Ok(leaves) if size <= maximum => Ok(leaves),
+            _ => Err(ProofError::SizeOutOfRange { size, maximum }),
+        }
+    }
+
+    /// Returns the root of the `size` leaves from `start`, which is a multiple
+    /// of the largest power of two not above `size`.
+    fn subtree(&self, start: usize, size: usize) -> [u8; 32] {
+        match size {
+            0 => EMPTY_ROOT,
+            size if size.is_power_of_two() => {
+                let level = size.trailing_zeros() as usize;
+
+                self.levels[level][start >> level]
+            }
+            size => {
+                let half = split(size);
+
+                node_hash(
+                    &self.subtree(start, half),
+                    &self.subtree(start + half, size - half),
+                )
+            }
+        }
+    }
+
+    /// Appends `PATH(index, D[start:start + size])` of RFC 6962.
+    fn path(&self, index: usize, start: usize, size: usize, proof: &mut Vec<[u8; 32]>) {
+        if size <= 1 {
+            return;
+        }
+
+        let half = split(size);
+
+        if index < half {
+            self.path(index, start, half, proof);
+
+            proof.push(self.subtree(start + half, size - half));
+        } else {
+            self.path(index - half, start + half, size - half, proof);
+
+            proof.push(self.subtree(start, half));
+        }
+    }
+
+    /// Appends `SUBPROOF(old_size, D[start:start + size], complete)` of
+    /// RFC 6962, `complete` telling whether the subtree of the old size is
+    /// known to the verifier.
+    fn subproof(
+        &self,
+        old_size: usize,
+        start: usize,
+        size: usize,
+        complete: bool,
+        proof: &mut Vec<[u8; 32]>,
+    ) {
+        if old_size == size {
+            if !complete {
+                proof.push(self.subtree(start, size));
+            }
+
+            return;
+        }
+
+        let half = split(size);
+
+        if old_size <= half {
+            self.subproof(old_size, start, half, complete, proof);
+
+            proof.push(self.subtree(start + half, size - half));
+        } else {
+            self.subproof(old_size - half, start + half, size - half, false, proof);
+
+            proof.push(self.subtree(start, half));
+        }
+    }
+}
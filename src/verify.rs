@@ -0,0 +1,99 @@
+//! Compile-time verification of embedded data against pinned digests.
+
+/// Fails compilation unless the SHA-224 digest of the bytes matches the pinned
+/// digest, written as hexadecimal digits or base64. Evaluates to the bytes.
+///
+/// See [`verify_sha256!`](crate::verify_sha256!) for an example.
+#[macro_export]
+macro_rules! verify_sha224 {
+    ($bytes: expr, $expected: literal $(,)?) => {
+        $crate::__verify_digest!(Sha224, "SHA-224", $bytes, $expected)
+    };
+}
+
+/// Fails compilation unless the SHA-256 digest of the bytes matches the pinned
+/// digest, written as hexadecimal digits or base64. Evaluates to the bytes.
+///
+/// ```
+/// static GREETING: &[u8] = compile_time_sha2::verify_sha256!(
+///     b"Hello, world!",
+///     "315f5bdb76d078c43b8ac0064e4a0164612b1fce77c869345bfc94c75894edd3",
+/// );
+/// ```
+///
+/// ```compile_fail
+/// static GREETING: &[u8] = compile_time_sha2::verify_sha256!(
+///     b"Hello, world?",
+///     "315f5bdb76d078c43b8ac0064e4a0164612b1fce77c869345bfc94c75894edd3",
+/// );
+/// ```
+///
+/// Large inputs, e.g. ones embedded with [`include_bytes!`], may trigger the
+/// `long_running_const_eval` lint, which can be allowed on the enclosing item.
+#[macro_export]
+macro_rules! verify_sha256 {
+    ($bytes: expr, $expected: literal $(,)?) => {
+        $crate::__verify_digest!(Sha256, "SHA-256", $bytes, $expected)
+    };
+}
+
+/// Fails compilation unless the SHA-384 digest of the bytes matches the pinned
+/// digest, written as hexadecimal digits or base64. Evaluates to the bytes.
+///
+/// See [`verify_sha256!`](crate::verify_sha256!) for an example.
+#[macro_export]
+macro_rules! verify_sha384 {
+    ($bytes: expr, $expected: literal $(,)?) => {
+        $crate::__verify_digest!(Sha384, "SHA-384", $bytes, $expected)
+    };
+}
+
+/// Fails compilation unless the SHA-512 digest of the bytes matches the pinned
+/// digest, written as hexadecimal digits or base64. Evaluates to the bytes.
+///
+/// See [`verify_sha256!`](crate::verify_sha256!) for an example.
+#[macro_export]
+macro_rules! verify_sha512 {
+    ($bytes: expr, $expected: literal $(,)?) => {
+        $crate::__verify_digest!(Sha512, "SHA-512", $bytes, $expected)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __verify_digest {
+    ($sha_type: ident, $name: literal, $bytes: expr, $expected: literal) => {{
+        const BYTES: &[u8] = $bytes;
+
+        const _: () = {
+            let Ok(expected) = $crate::encoding::decode_digest($expected) else {
+                panic!(concat!(
+                    "The pinned ",
+                    $name,
+                    " digest is neither valid hexadecimal nor base64: ",
+                    $expected,
+                ));
+            };
+
+            let Ok(actual) = $crate::$sha_type::digest(BYTES) else {
+                panic!(concat!(
+                    "The embedded bytes are too long to be processed by ",
+                    $name,
+                    "!",
+                ));
+            };
+
+            if !$crate::__private::ct_eq(&actual, &expected) {
+                panic!(concat!(
+                    "The ",
+                    $name,
+                    " digest of the embedded bytes does not match the pinned digest ",
+                    $expected,
+                    "!",
+                ));
+            }
+        };
+
+        BYTES
+    }};
+}
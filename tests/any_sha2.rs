@@ -0,0 +1,135 @@
+use compile_time_sha2::{
+    algorithm::Algorithm, error::ParseError, AnyDigest, AnySha2, Sha224, Sha256, Sha384, Sha512,
+};
+
+#[test]
+fn test_from_name() {
+    for (names, algorithm) in [
+        (
+            ["SHA-224", "sha-224", "SHA224", "sha2-224"],
+            Algorithm::Sha224,
+        ),
+        (
+            ["SHA-256", "sha-256", "Sha256", "SHA2-256"],
+            Algorithm::Sha256,
+        ),
+        (
+            ["SHA-384", "sha-384", "sha384", "sha2-384"],
+            Algorithm::Sha384,
+        ),
+        (
+            ["SHA-512", "sHa-512", "SHA512", "Sha2-512"],
+            Algorithm::Sha512,
+        ),
+    ] {
+        for name in names {
+            assert_eq!(Algorithm::from_name(name), Ok(algorithm), "{name}");
+            assert_eq!(name.parse(), Ok(algorithm), "{name}");
+            assert_eq!(AnySha2::from_name(name).unwrap().algorithm(), algorithm);
+        }
+
+        assert_eq!(Algorithm::from_name(algorithm.name()), Ok(algorithm));
+        assert_eq!(Algorithm::from_name(algorithm.iana_name()), Ok(algorithm));
+        assert_eq!(Algorithm::from_name(algorithm.tag_name()), Ok(algorithm));
+    }
+
+    for name in [
+        "",
+        "sha",
+        "256",
+        "sha-1",
+        "SHA1",
+        "sha2-",
+        "sha--256",
+        "sha-2-256",
+        "sha256 ",
+        "sha3-256",
+        "sha512/256",
+        "shé-256",
+    ] {
+        assert!(
+            matches!(AnySha2::from_name(name), Err(ParseError::UnknownAlgorithm)),
+            "{name}",
+        );
+    }
+}
+
+#[test]
+fn test_from_oid() {
+    for algorithm in Algorithm::ALL {
+        assert_eq!(Algorithm::from_oid(algorithm.oid()), Ok(algorithm));
+        assert_eq!(Algorithm::from_oid_der(algorithm.oid_der()), Ok(algorithm));
+    }
+
+    assert_eq!(
+        AnySha2::from_oid("2.16.840.1.101.3.4.2.3")
+            .unwrap()
+            .algorithm(),
+        Algorithm::Sha512,
+    );
+
+    assert_eq!(
+        AnySha2::from_oid_der(&[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x04])
+            .unwrap()
+            .algorithm(),
+        Algorithm::Sha224,
+    );
+
+    // SHA-512/256 and SHA-1.
+    assert!(AnySha2::from_oid("2.16.840.1.101.3.4.2.6").is_err());
+    assert!(AnySha2::from_oid_der(&[0x2B, 0x0E, 0x03, 0x02, 0x1A]).is_err());
+}
+
+#[test]
+fn test_digest() {
+    let message = b"The quick brown fox jumps over the lazy dog";
+
+    let (first, second) = message.split_at(10);
+
+    let digests = Algorithm::ALL.map(|algorithm| {
+        AnySha2::new(algorithm)
+            .update(first)
+            .unwrap()
+            .update(second)
+            .unwrap()
+            .finalize()
+    });
+
+    assert_eq!(
+        digests,
+        [
+            AnyDigest::Sha224(Sha224::digest(message).unwrap()),
+            AnyDigest::Sha256(Sha256::digest(message).unwrap()),
+            AnyDigest::Sha384(Sha384::digest(message).unwrap()),
+            AnyDigest::Sha512(Sha512::digest(message).unwrap()),
+        ],
+    );
+
+    for (algorithm, digest) in Algorithm::ALL.into_iter().zip(digests) {
+        assert_eq!(digest.algorithm(), algorithm);
+        assert_eq!(digest.as_bytes().len(), algorithm.output_length());
+        assert_eq!(AnySha2::digest(algorithm, message), Ok(digest));
+    }
+
+    assert_eq!(
+        digests[1].to_string(),
+        "d7a8fbb307d7809469ca9abcb0082e4f8d5651e46d3cdb762d02d0bf37c9e592",
+    );
+
+    assert_eq!(
+        format!("{:X}", digests[0]),
+        "730E109BD7A8A32B1CB9D9A09AA2325D2430587DDBC0C38BAD911525",
+    );
+}
+
+#[test]
+fn test_const() {
+    const DIGEST: AnyDigest = match AnySha2::digest(Algorithm::Sha256, b"abc") {
+        Ok(digest) => digest,
+        Err(_) => panic!("message too long"),
+    };
+
+    assert_eq!(DIGEST, AnyDigest::Sha256(Sha256::digest(b"abc").unwrap()));
+
+    assert_eq!(AnySha2::from(Sha384::new()).algorithm(), Algorithm::Sha384);
+}
@@ -0,0 +1,510 @@
+use compile_time_sha2::{
+    bitcoin::{
+        header::{BlockHeader, Target, HEADER_LENGTH},
+        merkle::{merkle_root, merkle_root_from_branch, verify_merkle_branch},
+        sha256d, sha256d_parts, TaggedHash,
+    },
+    encoding::decode_hex,
+    error::{MerkleError, ParseError, ProofOfWorkError},
+    Sha256,
+};
+
+const CHALLENGE: TaggedHash = TaggedHash::new(b"BIP0340/challenge");
+const AUX: TaggedHash = TaggedHash::new(b"BIP0340/aux");
+
+/// Signing vectors 0 to 3 of BIP-340, with the tagged hashes computed while
+/// signing: the auxiliary randomness hash and the challenge, whose inputs are
+/// both public. The expected hashes were computed with Python's `hashlib`
+/// while reproducing each signature from its secret key.
+struct Bip340Vector {
+    public_key: &'static str,
+    aux_rand: &'static str,
+    message: &'static str,
+    signature: &'static str,
+    aux_hash: &'static str,
+    challenge: &'static str,
+}
+
+const BIP340_VECTORS: [Bip340Vector; 4] = [
+    Bip340Vector {
+        public_key: "F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+        aux_rand: "0000000000000000000000000000000000000000000000000000000000000000",
+        message: "0000000000000000000000000000000000000000000000000000000000000000",
+        signature: "E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA8215\
+                    25F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0",
+        aux_hash: "54f169cfc9e2e5727480441f90ba25c488f461c70b5ea5dcaaf7af69270aa514",
+        challenge: "6bb6b93a91f2ecc0cd924f4f9baabb5e6eb21745bb00f2cebdaac908bb5d86ce",
+    },
+    Bip340Vector {
+        public_key: "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+        aux_rand: "0000000000000000000000000000000000000000000000000000000000000001",
+        message: "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+        signature: "6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE3341\
+                    8906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A",
+        aux_hash: "ee8790e3e65f5408c3f41b56a88c4e096126a71abb3a9ccce6efd19dafc7ec32",
+        challenge: "cfb58e748d9648b71fdc909fb7432fc0c954da5bd75cdc9d4804d32648f9839a",
+    },
+    Bip340Vector {
+        public_key: "DD308AFEC5777E13121FA72B9CC1B7CC0139715309B086C960E18FD969774EB8",
+        aux_rand: "C87AA53824B4D7AE2EB035A2B5BBBCCC080E76CDC6D1692C4B0B62D798E6D906",
+        message: "7E2D58D8B3BCDF1ABADEC7829054F90DDA9805AAB56C77333024B9D0A508B75C",
+        signature: "5831AAEED7B44BB74E5EAB94BA9D4294C49BCF2A60728D8B4C200F50DD313C1B\
+                    AB745879A5AD954A72C45A91C3A51D3C7ADEA98D82F8481E0E1E03674A6F3FB7",
+        aux_hash: "73db95cc3e3821f6695b492ef656ca7aadde058711eef8302141229fd3e8bd28",
+        challenge: "9bc1ba4a0abbc0792066b2ca0ef771d88af676b322a83dd7517f7c1fd149215a",
+    },
+    Bip340Vector {
+        public_key: "25D1DFF95105F5253C4022F628A996AD3A0D95FBF21D468A1B33F8C160D8F517",
+        aux_rand: "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+        message: "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+        signature: "7EB0509757E246F19449885651611CB965ECC1A187DD51B64FDA1EDC9637D5EC\
+                    97582B9CB13DB3933705B32BA982AF5AF25FD78881EBB32771FC5922EFC66EA3",
+        aux_hash: "fdb71327ced66d323510223cfa24d25565b63f462b9693fee13c3f16a1993194",
+        challenge: "a5352f4ded269e6716184547944a209417f759df44e61ea1d48008997c9003c0",
+    },
+];
+
+/// Mainnet headers of the genesis block, block 1 and block 100000, with
+/// their hashes as displayed, i.e. byte-reversed.
+const HEADERS: [(&str, &str); 3] = [
+    (
+        "0100000000000000000000000000000000000000000000000000000000000000\
+         000000003ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa\
+         4b1e5e4a29ab5f49ffff001d1dac2b7c",
+        "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f",
+    ),
+    (
+        "010000006fe28c0ab6f1b372c1a6a246ae63f74f931e8365e15a089c68d61900\
+         00000000982051fd1e4ba744bbbe680e1fee14677ba1a3c3540bf7b1cdb606e8\
+         57233e0e61bc6649ffff001d01e36299",
+        "00000000839a8e6886ab5951d76f411475428afc90947ee320161bbf18eb6048",
+    ),
+    (
+        "0100000050120119172a610421a6c3011dd330d9df07b63616c2cc1f1cd00200\
+         000000006657a9252aacd5c0b2940996ecff952228c3067cc38d4885efb5a4ac\
+         4247e9f337221b4d4c86041b0f2b5710",
+        "000000000003ba27aa200b1cecaad478d2b00432346c3f1f3986da1afd33e506",
+    ),
+];
+
+/// Transactions of block 100000, displayed byte-reversed.
+const BLOCK_100000_TXIDS: [&str; 4] = [
+    "8c14f0db3df150123e6f3dbbf30f8b955a8249b62ac1d1ff16284aefa3d06d87",
+    "fff2525b8931402dd09222c50775608f75787bd2b87e56995a7bdd30f79702c4",
+    "6359f0868171b1d194cbee1af2f16ea598ae8fad666d9b012c8ed2b79a236ec4",
+    "e9a66845e05d5abc0ad04ec80f774a7e585c6e8db975962d069a522137b80c1d",
+];
+
+fn bytes<const N: usize>(hex: &str) -> [u8; N] {
+    decode_hex(hex).unwrap()
+}
+
+fn reversed<const N: usize>(hex: &str) -> [u8; N] {
+    let mut bytes = bytes::<N>(hex);
+
+    bytes.reverse();
+
+    bytes
+}
+
+/// Builds the tree level by level, returning the root and the branch of the
+/// transaction at `index`.
+fn naive_tree(txids: &[[u8; 32]], mut index: usize) -> ([u8; 32], Vec<[u8; 32]>) {
+    let mut level = txids.to_vec();
+
+    let mut branch = Vec::new();
+
+    while level.len() > 1 {
+        if level.len() % 2 == 1 {
+            level.push(*level.last().unwrap());
+        }
+
+        branch.push(level[index ^ 1]);
+
+        level = level
+            .chunks(2)
+            .map(|pair| sha256d_parts(&[&pair[0], &pair[1]]).unwrap())
+            .collect();
+
+        index /= 2;
+    }
+
+    (level[0], branch)
+}
+
+#[test]
+fn test_sha256d() {
+    assert_eq!(
+        sha256d(b""),
+        Ok(bytes(
+            "5df6e0e2761359d30a8275058e299fcc0381534545f55cf43e41983f5d4c9456"
+        )),
+    );
+
+    assert_eq!(
+        sha256d(b"hello"),
+        Ok(bytes(
+            "9595c9df90075148eb06860365df33584b75bff782a510c6cd4883a419833d50"
+        )),
+    );
+
+    for length in [0, 55, 56, 64, 200] {
+        let message = vec![0x5A; length];
+
+        let expected = Sha256::digest(&Sha256::digest(&message).unwrap()).unwrap();
+
+        assert_eq!(sha256d(&message), Ok(expected));
+        assert_eq!(
+            sha256d_parts(&[&message[..length / 3], &message[length / 3..]]),
+            Ok(expected)
+        );
+    }
+}
+
+#[test]
+fn test_bip340_vectors() {
+    for vector in BIP340_VECTORS {
+        let signature = bytes::<64>(vector.signature);
+
+        let public_key = bytes::<32>(vector.public_key);
+
+        let message = bytes::<32>(vector.message);
+
+        assert_eq!(
+            AUX.hash(&bytes::<32>(vector.aux_rand)),
+            Ok(bytes(vector.aux_hash)),
+        );
+
+        assert_eq!(
+            CHALLENGE.hash_parts(&[&signature[..32], &public_key, &message]),
+            Ok(bytes(vector.challenge)),
+        );
+    }
+}
+
+#[test]
+fn test_tagged_hash() {
+    for tag in [&b""[..], b"BIP0340/nonce", b"TapLeaf", &[b't'; 100]] {
+        let tagged = TaggedHash::new(tag);
+
+        let tag_hash = Sha256::digest(tag).unwrap();
+
+        // The prefix fills a block, which is processed before any message.
+        let state = tagged.hasher().export_state();
+
+        assert_eq!(state.message_length(), 64);
+        assert_eq!(state.as_bytes().len(), 1 + 16 + 32);
+
+        for message in [&b""[..], b"abc", &[0xEE; 150]] {
+            let expected = Sha256::digest_parts(&[&tag_hash, &tag_hash, message]).unwrap();
+
+            assert_eq!(tagged.hash(message), Ok(expected));
+            assert_eq!(tagged.hash_parts(&[message, b""]), Ok(expected));
+            assert_eq!(
+                tagged.hasher().update(message).unwrap().finalize(),
+                expected
+            );
+        }
+    }
+}
+
+#[test]
+fn test_block_headers() {
+    for (header, hash) in HEADERS {
+        let serialized = bytes::<HEADER_LENGTH>(header);
+
+        let parsed = BlockHeader::parse(&serialized);
+
+        assert_eq!(parsed.version, 1);
+        assert_eq!(parsed.to_bytes(), serialized);
+        assert_eq!(BlockHeader::from_slice(&serialized), Ok(parsed));
+        assert_eq!(parsed.block_hash(), reversed(hash));
+        assert_eq!(Ok(parsed.block_hash()), sha256d(&serialized));
+        assert_eq!(parsed.check_proof_of_work(&Target::MAINNET_LIMIT), Ok(()));
+    }
+
+    let genesis = BlockHeader::parse(&bytes(HEADERS[0].0));
+
+    let first = BlockHeader::parse(&bytes(HEADERS[1].0));
+
+    assert_eq!(genesis.previous_block_hash, [0; 32]);
+    assert_eq!(first.previous_block_hash, genesis.block_hash());
+    assert_eq!(genesis.time, 1_231_006_505);
+    assert_eq!(genesis.bits, 0x1D00_FFFF);
+    assert_eq!(genesis.nonce, 2_083_236_893);
+
+    assert_eq!(
+        BlockHeader::from_slice(&[0; HEADER_LENGTH + 1]),
+        Err(ParseError::InvalidLength {
+            expected: HEADER_LENGTH,
+            actual: HEADER_LENGTH + 1,
+        }),
+    );
+    assert_eq!(
+        BlockHeader::from_slice(&[]),
+        Err(ParseError::InvalidLength {
+            expected: HEADER_LENGTH,
+            actual: 0,
+        }),
+    );
+}
+
+#[test]
+fn test_proof_of_work_failures() {
+    let genesis = BlockHeader::parse(&bytes(HEADERS[0].0));
+
+    let check = |bits: u32, nonce: u32| {
+        BlockHeader {
+            bits,
+            nonce,
+            ..genesis
+        }
+        .check_proof_of_work(&Target::MAINNET_LIMIT)
+    };
+
+    assert_eq!(
+        check(0x1D00_FFFF, genesis.nonce + 1),
+        Err(ProofOfWorkError::InsufficientWork),
+    );
+    assert_eq!(
+        check(0x1D01_0000, genesis.nonce),
+        Err(ProofOfWorkError::TargetAboveLimit),
+    );
+    assert_eq!(
+        check(0x1C80_0001, genesis.nonce),
+        Err(ProofOfWorkError::NegativeTarget),
+    );
+    assert_eq!(
+        check(0x2301_0000, genesis.nonce),
+        Err(ProofOfWorkError::TargetOverflow),
+    );
+    assert_eq!(
+        check(0x1D00_0000, genesis.nonce),
+        Err(ProofOfWorkError::ZeroTarget),
+    );
+
+    // Changing the bits changes the hash, thus lower targets are checked
+    // against the genesis hash directly.
+    let meets = |bits| {
+        Target::from_compact(bits)
+            .unwrap()
+            .is_met_by(&genesis.block_hash())
+    };
+
+    assert!(meets(0x1C00_FFFF));
+    assert!(meets(0x1B1A_0000));
+    assert!(!meets(0x1B19_D668));
+    assert!(!meets(0x1B00_FFFF));
+
+    // The limit is inclusive.
+    let mut limit = [0; 32];
+
+    limit.copy_from_slice(&genesis.block_hash());
+    limit.reverse();
+
+    assert!(Target::from_be_bytes(limit).is_met_by(&genesis.block_hash()));
+    assert_eq!(
+        genesis.check_proof_of_work(&Target::from_be_bytes(limit)),
+        Err(ProofOfWorkError::TargetAboveLimit),
+    );
+}
+
+#[test]
+fn test_compact_target() {
+    let target = |bits| Target::from_compact(bits).map(|target| target.to_be_bytes());
+
+    assert_eq!(target(0x1D00_FFFF), Ok(Target::MAINNET_LIMIT.to_be_bytes()));
+    assert_eq!(
+        target(0x1B04_864C),
+        Ok(bytes(
+            "000000000004864c000000000000000000000000000000000000000000000000"
+        )),
+    );
+    assert_eq!(target(0x0112_3456), Ok(bytes(&format!("{:064x}", 0x12))));
+    assert_eq!(target(0x0212_3456), Ok(bytes(&format!("{:064x}", 0x1234))));
+    assert_eq!(
+        target(0x0312_3456),
+        Ok(bytes(&format!("{:064x}", 0x0012_3456)))
+    );
+    assert_eq!(
+        target(0x0412_3456),
+        Ok(bytes(&format!("{:064x}", 0x1234_5600))),
+    );
+    assert_eq!(target(0x0000_0000), Ok([0; 32]));
+    assert_eq!(target(0x0080_0000), Ok([0; 32]));
+    assert_eq!(target(0x0180_0000), Ok([0; 32]));
+    assert_eq!(target(0x0300_0001), Ok(bytes(&format!("{:064x}", 1))));
+    assert_eq!(
+        target(0x2000_0001),
+        Ok(bytes(
+            "0000010000000000000000000000000000000000000000000000000000000000"
+        )),
+    );
+
+    let mut highest = [0; 32];
+
+    highest[0] = 0x7F;
+    highest[1] = 0xFF;
+    highest[2] = 0xFF;
+
+    assert_eq!(target(0x207F_FFFF), Ok(highest));
+    assert_eq!(
+        target(0x2100_00FF),
+        Ok(bytes(
+            "00ff000000000000000000000000000000000000000000000000000000000000"
+        ))
+    );
+    assert_eq!(target(0x2101_0000), Err(ProofOfWorkError::TargetOverflow));
+    assert_eq!(
+        target(0x2200_00FF),
+        Ok(bytes(
+            "ff00000000000000000000000000000000000000000000000000000000000000"
+        ))
+    );
+    assert_eq!(target(0x2200_0100), Err(ProofOfWorkError::TargetOverflow));
+    assert_eq!(target(0x2300_00FF), Err(ProofOfWorkError::TargetOverflow));
+    assert_eq!(target(0xFF00_0000), Ok([0; 32]));
+    assert_eq!(target(0x0492_3456), Err(ProofOfWorkError::NegativeTarget));
+
+    // Vectors of `SetCompact` in Bitcoin Core's `arith_uint256_tests.cpp`.
+    for bits in [
+        0x0012_3456,
+        0x0100_3456,
+        0x0200_0056,
+        0x0300_0000,
+        0x0400_0000,
+        0x0092_3456,
+        0x0180_3456,
+        0x0280_0056,
+        0x0380_0000,
+        0x0480_0000,
+    ] {
+        assert_eq!(target(bits), Ok([0; 32]), "{bits:#010x}");
+    }
+
+    assert_eq!(target(0x01FE_DCBA), Err(ProofOfWorkError::NegativeTarget));
+    assert_eq!(
+        target(0x0500_9234),
+        Ok(bytes(&format!("{:064x}", 0x9234_0000_u32))),
+    );
+    assert_eq!(
+        target(0x2012_3456),
+        Ok(bytes(
+            "1234560000000000000000000000000000000000000000000000000000000000"
+        )),
+    );
+    assert_eq!(target(0xFF12_3456), Err(ProofOfWorkError::TargetOverflow));
+}
+
+#[test]
+fn test_block_merkle_roots() {
+    for (header, _) in &HEADERS[..2] {
+        let header = BlockHeader::parse(&bytes(header));
+
+        // The only transaction of both blocks is their coinbase.
+        assert_eq!(merkle_root(&[header.merkle_root]), Ok(header.merkle_root));
+    }
+
+    let header = BlockHeader::parse(&bytes(HEADERS[2].0));
+
+    let txids = BLOCK_100000_TXIDS.map(reversed::<32>);
+
+    assert_eq!(merkle_root(&txids), Ok(header.merkle_root));
+    assert_eq!(
+        header.merkle_root,
+        reversed("f3e94742aca4b5ef85488dc37c06c3282295ffec960994b2c0d5ac2a25a95766"),
+    );
+
+    for (index, txid) in txids.iter().enumerate() {
+        let (_, branch) = naive_tree(&txids, index);
+
+        assert!(verify_merkle_branch(
+            txid,
+            &branch,
+            index,
+            &header.merkle_root
+        ));
+        assert!(!verify_merkle_branch(
+            txid,
+            &branch,
+            index ^ 1,
+            &header.merkle_root
+        ));
+    }
+
+    assert_eq!(merkle_root(&[]), Err(MerkleError::NoTransactions));
+}
+
+#[test]
+fn test_merkle_mutation() {
+    let txids = (0_u8..11)
+        .map(|index| Sha256::digest(&[index]).unwrap())
+        .collect::<Vec<_>>();
+
+    for length in 1..=txids.len() {
+        let txids = &txids[..length];
+
+        let (root, _) = naive_tree(txids, 0);
+
+        assert_eq!(merkle_root(txids), Ok(root));
+
+        // Duplicating the last transactions of an odd level keeps the root.
+        if length % 2 == 1 && length > 1 {
+            let mutated = [txids, &txids[length - 1..]].concat();
+
+            assert_eq!(naive_tree(&mutated, 0).0, root);
+            assert_eq!(merkle_root(&mutated), Err(MerkleError::Mutated));
+        }
+    }
+
+    // Six transactions pair their last two nodes at the second level, thus
+    // duplicating the last two transactions yields the same root.
+    let (root, _) = naive_tree(&txids[..6], 0);
+
+    let mutated = [&txids[..6], &txids[4..6]].concat();
+
+    assert_eq!(naive_tree(&mutated, 0).0, root);
+    assert_eq!(merkle_root(&mutated), Err(MerkleError::Mutated));
+
+    // Equal siblings are rejected even when they do not collide with
+    // another list.
+    assert_eq!(
+        merkle_root(&[txids[0], txids[0]]),
+        Err(MerkleError::Mutated)
+    );
+    assert_eq!(
+        merkle_root(&[txids[0], txids[1], txids[2], txids[2], txids[3]]),
+        Err(MerkleError::Mutated),
+    );
+}
+
+#[test]
+fn test_merkle_branches() {
+    let txids = (0_u16..37)
+        .map(|index| Sha256::digest(&index.to_le_bytes()).unwrap())
+        .collect::<Vec<_>>();
+
+    for length in [1, 2, 3, 5, 8, 13, 37] {
+        let txids = &txids[..length];
+
+        let root = merkle_root(txids).unwrap();
+
+        for (index, txid) in txids.iter().enumerate() {
+            let (naive_root, branch) = naive_tree(txids, index);
+
+            assert_eq!(naive_root, root);
+            assert_eq!(merkle_root_from_branch(txid, &branch, index), root);
+            assert!(verify_merkle_branch(txid, &branch, index, &root));
+
+            if !branch.is_empty() {
+                assert!(!verify_merkle_branch(txid, &branch[1..], index, &root));
+                assert!(!verify_merkle_branch(
+                    &txids[(index + 1) % length],
+                    &branch,
+                    index,
+                    &root
+                ));
+            }
+        }
+    }
+}
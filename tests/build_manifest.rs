@@ -0,0 +1,170 @@
+#![cfg(feature = "std")]
+
+use std::{fs, path::Path, thread, time::Duration};
+
+use compile_time_sha2::{algorithm::Algorithm, build::ManifestBuilder, Sha256};
+
+use self::common::TemporaryDirectory;
+
+mod common;
+
+/// Manifest generated from `tests/manifest/assets`, checked in to make sure
+/// the generated source compiles.
+mod manifest {
+    include!("manifest/assets.rs");
+}
+
+fn assets(name: &str) -> TemporaryDirectory {
+    let directory = TemporaryDirectory::new(name);
+
+    directory.file("site.css", "body {}\n");
+    directory.file("scripts/z/app.js", "export {};\n");
+    directory.file("scripts/a.js", "export {};\n");
+    directory.file("vendor/lib.js", "export {};\n");
+    directory.file("README.txt", "Assets\n");
+
+    directory
+}
+
+#[test]
+fn test_generated_manifest() {
+    let directory = assets("generated_manifest");
+
+    let source = ManifestBuilder::new(&directory.0)
+        .include("**/*.css")
+        .include("**/*.js")
+        .exclude("vendor/**")
+        .generate()
+        .unwrap();
+
+    assert_eq!(
+        source,
+        r#"// @generated by compile_time_sha2::build. Do not edit.
+
+pub const ALGORITHM: &str = "SHA-256";
+
+pub struct Entry {
+    pub path: &'static str,
+    pub length: u64,
+    pub digest: [u8; 32],
+}
+
+/// Entries sorted by path.
+pub const ENTRIES: &[Entry] = &[
+    Entry {
+        path: "scripts/a.js",
+        length: 11,
+        digest: *b"\x8e\x60\x9b\xb7\x1c\x20\xb8\x58\xc7\x7f\x0e\x9f\x90\xbb\x13\x19\xdb\x84\x77\xb1\x3f\x9f\x96\x5f\x1a\x1e\x18\x52\x4b\xf5\x08\x81",
+    },
+    Entry {
+        path: "scripts/z/app.js",
+        length: 11,
+        digest: *b"\x8e\x60\x9b\xb7\x1c\x20\xb8\x58\xc7\x7f\x0e\x9f\x90\xbb\x13\x19\xdb\x84\x77\xb1\x3f\x9f\x96\x5f\x1a\x1e\x18\x52\x4b\xf5\x08\x81",
+    },
+    Entry {
+        path: "site.css",
+        length: 8,
+        digest: *b"\xa0\x6f\xd7\x50\xde\x73\x74\x98\x3d\xaf\x40\x01\x65\x64\xb1\xfb\x6f\x21\x68\xed\x2c\x57\x42\xcc\xf6\x99\x12\xe8\x57\x48\x03\xc0",
+    },
+];
+
+pub fn get(path: &str) -> Option<&'static Entry> {
+    ENTRIES
+        .binary_search_by(|entry| entry.path.cmp(path))
+        .ok()
+        .map(|index| &ENTRIES[index])
+}
+"#,
+    );
+}
+
+#[test]
+fn test_manifest_algorithm_and_default_selection() {
+    let directory = assets("manifest_algorithm");
+
+    let entries = ManifestBuilder::new(&directory.0)
+        .algorithm(Algorithm::Sha512)
+        .entries()
+        .unwrap();
+
+    assert_eq!(
+        entries
+            .iter()
+            .map(|entry| entry.path.as_str())
+            .collect::<Vec<_>>(),
+        [
+            "README.txt",
+            "scripts/a.js",
+            "scripts/z/app.js",
+            "site.css",
+            "vendor/lib.js",
+        ],
+    );
+
+    assert_eq!(
+        entries[3].digest,
+        b"\x3d\xe6\x78\xed\x92\x18\x93\xee\x10\x0a\xd2\xed\xff\x61\x0c\xf3\x4b\x3a\x1f\x8e\xa8\x8a\xe4\x79\xb0\x69\xcb\x77\x50\xf4\xc8\x86\x0c\xdc\x47\x86\xa7\xff\x59\x3a\xb4\x7a\xb1\x40\x7f\xe9\x19\x5d\xb3\x45\xea\x44\x33\x76\x5d\x35\xbe\xf6\x21\x3d\x35\xe7\x36\xdd",
+    );
+}
+
+#[test]
+fn test_included_manifest() {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/manifest");
+
+    assert_eq!(
+        ManifestBuilder::new(directory.join("assets"))
+            .generate()
+            .unwrap(),
+        fs::read_to_string(directory.join("assets.rs")).unwrap(),
+        "tests/manifest/assets.rs is out of date",
+    );
+
+    assert_eq!(manifest::ALGORITHM, "SHA-256");
+
+    assert_eq!(
+        manifest::ENTRIES
+            .iter()
+            .map(|entry| entry.path)
+            .collect::<Vec<_>>(),
+        ["scripts/app.js", "site.css"],
+    );
+
+    let entry = manifest::get("site.css").unwrap();
+
+    assert_eq!(entry.length, 8);
+
+    assert_eq!(
+        entry.digest,
+        Sha256::new().update(b"body {}\n").unwrap().finalize(),
+    );
+
+    assert!(manifest::get("missing.css").is_none());
+}
+
+#[test]
+fn test_write_leaves_unchanged_file_untouched() {
+    let directory = assets("write_unchanged");
+
+    let output = directory.0.join("manifest.rs");
+
+    let builder = ManifestBuilder::new(directory.0.join("scripts"));
+
+    builder.write(&output).unwrap();
+
+    let modified = fs::metadata(&output).unwrap().modified().unwrap();
+
+    thread::sleep(Duration::from_millis(50));
+
+    builder.write(&output).unwrap();
+
+    assert_eq!(fs::metadata(&output).unwrap().modified().unwrap(), modified);
+
+    directory.file("scripts/a.js", "export default {};\n");
+
+    builder.write(&output).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&output).unwrap(),
+        builder.generate().unwrap(),
+    );
+}
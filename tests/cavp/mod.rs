@@ -0,0 +1,165 @@
+//! Parser for the response (`.rsp`) files of the NIST CAVP SHA test vectors,
+//! as described in "The Secure Hash Algorithm Validation System (SHAVS)".
+
+use compile_time_sha2::{algorithm::Algorithm, encoding::decode_hex_into, AnySha2};
+
+/// Number of checkpoints in a Monte Carlo test.
+pub const MONTE_CARLO_CHECKPOINTS: usize = 100;
+
+/// Number of digests computed between two checkpoints of a Monte Carlo test.
+pub const MONTE_CARLO_ITERATIONS: usize = 1000;
+
+pub struct MessageVector {
+    pub length_in_bits: usize,
+    pub message: Vec<u8>,
+    pub digest: Vec<u8>,
+}
+
+pub struct MonteCarloVector {
+    pub seed: Vec<u8>,
+    pub checkpoints: Vec<Vec<u8>>,
+}
+
+pub enum Vectors {
+    /// Vectors of the `ShortMsg` and `LongMsg` files.
+    Messages(Vec<MessageVector>),
+    /// Vector of the `Monte` files.
+    MonteCarlo(MonteCarloVector),
+}
+
+pub struct ResponseFile {
+    /// Length of the digest in bytes, as given by the `[L = ...]` section.
+    pub output_length: usize,
+    pub vectors: Vectors,
+}
+
+/// Parses a response file, returning a description of the offending line on
+/// failure.
+pub fn parse(text: &str) -> Result<ResponseFile, String> {
+    let mut output_length = None;
+
+    let mut length_in_bits: Option<usize> = None;
+
+    let mut message = None;
+
+    let mut messages = Vec::new();
+
+    let mut seed = None;
+
+    let mut checkpoints = Vec::new();
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let error = |reason: &str| format!("line {}: {reason}: {line}", index + 1);
+
+        if let Some(section) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            let Some(length) = section.strip_prefix("L = ") else {
+                return Err(error("unknown section"));
+            };
+
+            output_length = Some(length.parse().map_err(|_| error("invalid length"))?);
+
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once(" = ") else {
+            return Err(error("expected a key and a value"));
+        };
+
+        match key {
+            "Len" => {
+                length_in_bits = Some(value.parse().map_err(|_| error("invalid length"))?);
+            }
+            "Msg" => message = Some(decode(value).ok_or_else(|| error("invalid message"))?),
+            "Seed" => seed = Some(decode(value).ok_or_else(|| error("invalid seed"))?),
+            "COUNT" => {}
+            "MD" => {
+                let digest = decode(value).ok_or_else(|| error("invalid digest"))?;
+
+                if seed.is_some() {
+                    checkpoints.push(digest);
+
+                    continue;
+                }
+
+                let (Some(length_in_bits), Some(mut message)) =
+                    (length_in_bits.take(), message.take())
+                else {
+                    return Err(error("digest is not preceded by a length and a message"));
+                };
+
+                // A zero-length message is still written as a single byte.
+                message.truncate((length_in_bits + 7) / 8);
+
+                messages.push(MessageVector {
+                    length_in_bits,
+                    message,
+                    digest,
+                });
+            }
+            _ => return Err(error("unknown key")),
+        }
+    }
+
+    let Some(output_length) = output_length else {
+        return Err(String::from("missing digest length section"));
+    };
+
+    let vectors = match seed {
+        Some(seed) => Vectors::MonteCarlo(MonteCarloVector { seed, checkpoints }),
+        None => Vectors::Messages(messages),
+    };
+
+    Ok(ResponseFile {
+        output_length,
+        vectors,
+    })
+}
+
+fn decode(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = vec![0; text.len() / 2];
+
+    decode_hex_into(text, &mut bytes).ok().map(|()| bytes)
+}
+
+pub fn digest(algorithm: Algorithm, message: &[u8]) -> Vec<u8> {
+    AnySha2::digest(algorithm, message)
+        .unwrap()
+        .as_bytes()
+        .to_vec()
+}
+
+/// Runs the pseudorandomly generated messages test of SHAVS, returning the
+/// digest recorded at each of the `checkpoints`, to be compared with those of
+/// a `Monte` file.
+///
+/// Every digest is computed over the concatenation of the three previous
+/// ones, starting from three copies of the seed, and the last digest before a
+/// checkpoint seeds the next round.
+pub fn monte_carlo(algorithm: Algorithm, seed: &[u8], checkpoints: usize) -> Vec<Vec<u8>> {
+    let mut seed = seed.to_vec();
+
+    (0..checkpoints)
+        .map(|_| {
+            let [mut first, mut second, mut third] = [seed.clone(), seed.clone(), seed.clone()];
+
+            for _ in 0..MONTE_CARLO_ITERATIONS {
+                let message = [first, second.clone(), third.clone()].concat();
+
+                (first, second, third) = (second, third, digest(algorithm, &message));
+            }
+
+            seed = third;
+
+            seed.clone()
+        })
+        .collect()
+}
@@ -0,0 +1,196 @@
+#![cfg(feature = "std")]
+
+use std::fs;
+
+use compile_time_sha2::{
+    algorithm::Algorithm,
+    checksums::{ChecksumEntry, ChecksumFile, EntryFormat, VerificationStatus},
+};
+
+use self::common::TemporaryDirectory;
+
+mod common;
+
+const ABC_SHA256: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+const ABC_SHA512: &str = "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f";
+
+fn manifest() -> String {
+    format!(
+        "# Release 1.0
+{ABC_SHA256}  abc.txt
+{ABC_SHA256} *abc.bin
+SHA512 (abc.tagged) = {ABC_SHA512}
+\\{ABC_SHA256}  a\\\\b\\nc.txt
+{ABC_SHA256}  other.txt
+{ABC_SHA256}  missing.txt
+garbage\r
+SHA1 (abc.txt) = a9993e364706816aba3e25717850c26c9cd0d89d
+"
+    )
+}
+
+#[test]
+fn test_parse() {
+    let file = ChecksumFile::parse(&manifest(), Algorithm::Sha256);
+
+    let formats = file
+        .entries()
+        .iter()
+        .map(|entry| (entry.file_name.as_str(), entry.algorithm, entry.format))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        formats,
+        [
+            ("abc.txt", Algorithm::Sha256, EntryFormat::Text),
+            ("abc.bin", Algorithm::Sha256, EntryFormat::Binary),
+            ("abc.tagged", Algorithm::Sha512, EntryFormat::Tagged),
+            ("a\\b\nc.txt", Algorithm::Sha256, EntryFormat::Text),
+            ("other.txt", Algorithm::Sha256, EntryFormat::Text),
+            ("missing.txt", Algorithm::Sha256, EntryFormat::Text),
+        ],
+    );
+
+    assert_eq!(file.improperly_formatted_lines(), [8, 9]);
+
+    assert!(ChecksumEntry::parse(&format!("{ABC_SHA256}  "), Algorithm::Sha256).is_none());
+    assert!(ChecksumEntry::parse(&format!("{ABC_SHA256}  a"), Algorithm::Sha512).is_none());
+    assert!(ChecksumEntry::parse(&format!("\\{ABC_SHA256}  a\\x"), Algorithm::Sha256).is_none());
+}
+
+#[test]
+fn test_parse_parenthesized_file_names() {
+    for (line, file_name, format) in [
+        (
+            format!("{ABC_SHA256}  My File (copy).txt"),
+            "My File (copy).txt",
+            EntryFormat::Text,
+        ),
+        (
+            format!("{ABC_SHA256} *notes (draft) = final.txt"),
+            "notes (draft) = final.txt",
+            EntryFormat::Binary,
+        ),
+        (
+            format!("SHA256 (My File (copy).txt) = {ABC_SHA256}"),
+            "My File (copy).txt",
+            EntryFormat::Tagged,
+        ),
+    ] {
+        let entry = ChecksumEntry::parse(&line, Algorithm::Sha256).unwrap();
+
+        assert_eq!(entry.algorithm, Algorithm::Sha256);
+        assert_eq!(entry.file_name, file_name);
+        assert_eq!(entry.format, format);
+        assert_eq!(entry.to_string(), line);
+    }
+
+    assert!(
+        ChecksumEntry::parse(&format!("MD5 (abc.txt) = {ABC_SHA256}"), Algorithm::Sha256).is_none()
+    );
+}
+
+#[test]
+fn test_round_trip() {
+    let file = ChecksumFile::parse(&manifest(), Algorithm::Sha256);
+
+    let written = file.to_string();
+
+    assert_eq!(
+        written,
+        manifest()
+            .lines()
+            .skip(1)
+            .take(6)
+            .map(|line| format!("{line}\n"))
+            .collect::<String>(),
+    );
+
+    assert_eq!(
+        ChecksumFile::parse(&written, Algorithm::Sha256).entries(),
+        file.entries(),
+    );
+}
+
+#[test]
+fn test_generate() {
+    let mut file = ChecksumFile::new();
+
+    for (file_name, format) in [
+        ("abc.txt", EntryFormat::Text),
+        ("abc.bin", EntryFormat::Tagged),
+    ] {
+        file.push(
+            ChecksumEntry::from_reader(Algorithm::Sha256, file_name, format, &b"abc"[..]).unwrap(),
+        );
+    }
+
+    assert_eq!(
+        file.to_string(),
+        format!("{ABC_SHA256}  abc.txt\nSHA256 (abc.bin) = {ABC_SHA256}\n"),
+    );
+}
+
+#[test]
+fn test_verify_data() {
+    let file = ChecksumFile::parse(&manifest(), Algorithm::Sha256);
+
+    let report = file.verify_data(|file_name| match file_name {
+        "missing.txt" => None,
+        "other.txt" => Some(b"other"),
+        _ => Some(b"abc"),
+    });
+
+    let statuses = report
+        .entries()
+        .iter()
+        .map(|report| match report.status() {
+            VerificationStatus::Matched => "matched",
+            VerificationStatus::Mismatched => "mismatched",
+            VerificationStatus::Missing => "missing",
+            VerificationStatus::Unreadable(_) => "unreadable",
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        statuses,
+        [
+            "matched",
+            "matched",
+            "matched",
+            "matched",
+            "mismatched",
+            "missing"
+        ],
+    );
+
+    assert!(!report.is_success());
+    assert_eq!(report.mismatched(), 1);
+    assert_eq!(report.missing(), 1);
+    assert_eq!(report.unreadable(), 0);
+    assert_eq!(report.entries()[4].entry().file_name, "other.txt");
+}
+
+#[test]
+fn test_verify_files() {
+    let directory = TemporaryDirectory::new("checksums");
+
+    directory.file("abc.txt", "abc");
+    directory.file("abc.bin", "abc");
+
+    let file = ChecksumFile::parse(
+        &format!("{ABC_SHA256}  abc.txt\n{ABC_SHA256} *abc.bin\n"),
+        Algorithm::Sha256,
+    );
+
+    assert!(file.verify_files(&directory.0).is_success());
+
+    directory.file("abc.bin", "abd");
+
+    assert_eq!(file.verify_files(&directory.0).mismatched(), 1);
+
+    fs::remove_file(directory.0.join("abc.bin")).unwrap();
+
+    assert_eq!(file.verify_files(&directory.0).missing(), 1);
+}
@@ -0,0 +1,36 @@
+use std::{fs, path::PathBuf};
+
+/// Directory under the system temporary directory, removed on drop.
+pub struct TemporaryDirectory(pub PathBuf);
+
+impl TemporaryDirectory {
+    /// Creates an empty directory whose name includes the process ID, so that
+    /// concurrent test runs do not collide.
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!(
+            "compile_time_sha2-{name}-{pid}",
+            pid = std::process::id(),
+        ));
+
+        _ = fs::remove_dir_all(&path);
+
+        fs::create_dir_all(&path).unwrap();
+
+        Self(path)
+    }
+
+    /// Writes a file, creating its parent directories.
+    pub fn file(&self, relative_path: &str, contents: &str) {
+        let path = self.0.join(relative_path);
+
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+        fs::write(path, contents).unwrap();
+    }
+}
+
+impl Drop for TemporaryDirectory {
+    fn drop(&mut self) {
+        _ = fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,257 @@
+#![cfg(feature = "std")]
+
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+use self::common::TemporaryDirectory;
+
+mod common;
+
+/// Runs the binary inside the directory.
+fn run(directory: &TemporaryDirectory, arguments: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ctsha2sum"))
+        .args(arguments)
+        .current_dir(&directory.0)
+        .output()
+        .unwrap()
+}
+
+const ABC_SHA256: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+const ABC_SHA512: &str = "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f";
+
+fn stdout(output: &Output) -> &str {
+    std::str::from_utf8(&output.stdout).unwrap()
+}
+
+fn stderr(output: &Output) -> &str {
+    std::str::from_utf8(&output.stderr).unwrap()
+}
+
+#[test]
+fn test_generate() {
+    let directory = TemporaryDirectory::new("ctsha2sum_generate");
+
+    directory.file("abc.txt", "abc");
+
+    let output = run(&directory, &["abc.txt"]);
+
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), format!("{ABC_SHA256}  abc.txt\n"));
+
+    let output = run(&directory, &["-b", "abc.txt"]);
+
+    assert_eq!(stdout(&output), format!("{ABC_SHA256} *abc.txt\n"));
+
+    let output = run(&directory, &["--tag", "-a", "512", "abc.txt"]);
+
+    assert_eq!(
+        stdout(&output),
+        format!("SHA512 (abc.txt) = {ABC_SHA512}\n")
+    );
+}
+
+#[test]
+fn test_generate_from_stdin() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_ctsha2sum"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child.stdin.take().unwrap().write_all(b"abc").unwrap();
+
+    let output = child.wait_with_output().unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), format!("{ABC_SHA256}  -\n"));
+}
+
+#[test]
+fn test_generate_escaped_file_name() {
+    let directory = TemporaryDirectory::new("ctsha2sum_escaped");
+
+    directory.file("a\\b.txt", "abc");
+
+    let output = run(&directory, &["a\\b.txt"]);
+
+    assert_eq!(stdout(&output), format!("\\{ABC_SHA256}  a\\\\b.txt\n"));
+
+    directory.file("sums", stdout(&output));
+
+    let output = run(&directory, &["-c", "sums"]);
+
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "a\\b.txt: OK\n");
+}
+
+#[test]
+fn test_check_parenthesized_file_name() {
+    let directory = TemporaryDirectory::new("ctsha2sum_parenthesized");
+
+    directory.file("My File (copy).txt", "abc");
+
+    let output = run(&directory, &["My File (copy).txt"]);
+
+    assert_eq!(
+        stdout(&output),
+        format!("{ABC_SHA256}  My File (copy).txt\n")
+    );
+
+    directory.file("sums", stdout(&output));
+
+    let output = run(&directory, &["-c", "--strict", "sums"]);
+
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "My File (copy).txt: OK\n");
+}
+
+#[test]
+fn test_check() {
+    let directory = TemporaryDirectory::new("ctsha2sum_check");
+
+    directory.file("abc.txt", "abc");
+    directory.file("other.txt", "other");
+    directory.file(
+        "sums",
+        &format!(
+            "# comment\n{ABC_SHA256}  abc.txt\nSHA512 (abc.txt) = {ABC_SHA512}\n{ABC_SHA256} *other.txt\n",
+        ),
+    );
+
+    let output = run(&directory, &["--check", "sums"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        "abc.txt: OK\nabc.txt: OK\nother.txt: FAILED\n",
+    );
+    assert_eq!(
+        stderr(&output),
+        "ctsha2sum: WARNING: 1 computed checksum did NOT match\n",
+    );
+
+    let output = run(&directory, &["--check", "--quiet", "sums"]);
+
+    assert_eq!(stdout(&output), "other.txt: FAILED\n");
+
+    let output = run(&directory, &["--check", "--status", "sums"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "");
+    assert_eq!(stderr(&output), "");
+}
+
+#[test]
+fn test_check_mismatched_tag() {
+    let directory = TemporaryDirectory::new("ctsha2sum_mismatched_tag");
+
+    directory.file("abc.txt", "abc");
+    directory.file(
+        "sums",
+        &format!("{ABC_SHA256}  abc.txt\nSHA512 (abc.txt) = {ABC_SHA512}\n"),
+    );
+
+    let output = run(&directory, &["-c", "-a", "256", "-w", "sums"]);
+
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "abc.txt: OK\n");
+    assert_eq!(
+        stderr(&output),
+        "ctsha2sum: sums: 2: improperly formatted SHA256 checksum line\nctsha2sum: WARNING: 1 line is improperly formatted\n",
+    );
+
+    let output = run(&directory, &["-c", "-a", "256", "--strict", "sums"]);
+
+    assert_eq!(output.status.code(), Some(1));
+
+    directory.file("sums", &format!("SHA512 (abc.txt) = {ABC_SHA512}\n"));
+
+    let output = run(&directory, &["-c", "--algorithm=256", "sums"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stderr(&output),
+        "ctsha2sum: sums: no properly formatted SHA256 checksum lines found\n",
+    );
+
+    let output = run(&directory, &["-c", "-a", "512", "sums"]);
+
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "abc.txt: OK\n");
+}
+
+#[test]
+fn test_check_improperly_formatted() {
+    let directory = TemporaryDirectory::new("ctsha2sum_improperly_formatted");
+
+    directory.file("abc.txt", "abc");
+    directory.file("sums", &format!("{ABC_SHA256}  abc.txt\ngarbage\n"));
+
+    let output = run(&directory, &["-c", "sums"]);
+
+    assert!(output.status.success());
+    assert_eq!(
+        stderr(&output),
+        "ctsha2sum: WARNING: 1 line is improperly formatted\n",
+    );
+
+    let output = run(&directory, &["-c", "--strict", "sums"]);
+
+    assert_eq!(output.status.code(), Some(1));
+
+    let output = run(&directory, &["-cw", "sums"]);
+
+    assert!(stderr(&output)
+        .starts_with("ctsha2sum: sums: 2: improperly formatted SHA256 checksum line\n"));
+
+    directory.file("empty", "garbage\n");
+
+    let output = run(&directory, &["-c", "empty"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stderr(&output),
+        "ctsha2sum: empty: no properly formatted SHA256 checksum lines found\n",
+    );
+}
+
+#[test]
+fn test_check_missing_files() {
+    let directory = TemporaryDirectory::new("ctsha2sum_missing");
+
+    directory.file("abc.txt", "abc");
+    directory.file(
+        "sums",
+        &format!("{ABC_SHA256}  abc.txt\n{ABC_SHA256}  missing.txt\n"),
+    );
+
+    let output = run(&directory, &["-c", "sums"]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        "abc.txt: OK\nmissing.txt: FAILED open or read\n",
+    );
+
+    let output = run(&directory, &["-c", "--ignore-missing", "sums"]);
+
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "abc.txt: OK\n");
+}
+
+#[test]
+fn test_invalid_arguments() {
+    let directory = TemporaryDirectory::new("ctsha2sum_invalid_arguments");
+
+    assert_eq!(run(&directory, &["--bogus"]).status.code(), Some(1));
+    assert_eq!(run(&directory, &["-a", "1024"]).status.code(), Some(1));
+    assert_eq!(run(&directory, &["--quiet"]).status.code(), Some(1));
+    assert_eq!(
+        run(&directory, &["--check", "--tag"]).status.code(),
+        Some(1)
+    );
+    assert!(run(&directory, &["--help"]).status.success());
+}
@@ -0,0 +1,105 @@
+use compile_time_sha2::{algorithm::Algorithm, AnySha2, Sha224, Sha256, Sha384, Sha512};
+
+const MESSAGE: &[u8] = b"abc";
+
+#[test]
+fn test_finalize_truncated() {
+    let sha = Sha256::new().update(MESSAGE).unwrap();
+
+    let full = sha.finalize();
+
+    // SHA-256/192, as used by LMS.
+    assert_eq!(sha.finalize_truncated::<24>(), full[..24]);
+
+    assert_eq!(sha.finalize_truncated::<8>(), full[..8]);
+
+    assert_eq!(sha.finalize_truncated::<32>(), full);
+
+    assert_eq!(sha.finalize_truncated::<0>(), []);
+
+    assert_eq!(
+        Sha384::new().finalize_truncated::<16>(),
+        Sha384::digest(b"").unwrap()[..16],
+    );
+}
+
+#[test]
+fn test_finalize_into() {
+    for algorithm in Algorithm::ALL {
+        let expected = AnySha2::digest(algorithm, MESSAGE).unwrap();
+
+        for length in [0, 1, 7, 8, 9, algorithm.output_length()] {
+            let mut output = vec![0; length];
+
+            AnySha2::new(algorithm)
+                .update(MESSAGE)
+                .unwrap()
+                .finalize_into(&mut output);
+
+            assert_eq!(output, expected.as_bytes()[..length], "{algorithm}");
+        }
+    }
+}
+
+#[test]
+#[should_panic = "Output buffer is longer than the digest!"]
+fn test_finalize_into_too_long() {
+    Sha224::new().finalize_into(&mut [0; 29]);
+}
+
+#[test]
+fn test_finalize_words() {
+    assert_eq!(
+        Sha256::new().update(MESSAGE).unwrap().finalize_words(),
+        [
+            0xBA78_16BF,
+            0x8F01_CFEA,
+            0x4141_40DE,
+            0x5DAE_2223,
+            0xB003_61A3,
+            0x9617_7A9C,
+            0xB410_FF61,
+            0xF200_15AD,
+        ],
+    );
+
+    let words = Sha224::new().update(MESSAGE).unwrap().finalize_words();
+
+    assert_eq!(words.len(), 7);
+
+    assert!(words
+        .iter()
+        .flat_map(|word| word.to_be_bytes())
+        .eq(Sha224::digest(MESSAGE).unwrap()));
+
+    let words = Sha384::new().update(MESSAGE).unwrap().finalize_words();
+
+    assert_eq!(words.len(), 6);
+
+    assert!(words
+        .iter()
+        .flat_map(|word| word.to_be_bytes())
+        .eq(Sha384::digest(MESSAGE).unwrap()));
+
+    assert!(Sha512::new()
+        .update(MESSAGE)
+        .unwrap()
+        .finalize_words()
+        .iter()
+        .flat_map(|word| word.to_be_bytes())
+        .eq(Sha512::digest(MESSAGE).unwrap()));
+}
+
+#[test]
+fn test_const() {
+    const ID: [u8; 8] = match Sha256::new().update(MESSAGE) {
+        Ok(sha) => sha.finalize_truncated(),
+        Err(_) => panic!("message too long"),
+    };
+
+    const WORDS: [u64; 8] = Sha512::new().finalize_words();
+
+    assert_eq!(ID, Sha256::digest(MESSAGE).unwrap()[..8]);
+
+    assert_eq!(WORDS[0], 0xCF83_E135_7EEF_B8BD);
+}
@@ -1,4 +1,4 @@
-use std::{fmt::Debug, num::NonZeroUsize};
+use std::num::NonZeroUsize;
 
 #[cfg(not(miri))]
 use proptest::proptest;
@@ -6,13 +6,12 @@ use proptest::proptest;
 use quickcheck_macros::quickcheck;
 use sha2::Digest;
 
-use compile_time_sha2::{Sha224, Sha256, Sha384, Sha512};
+use compile_time_sha2::{Sha224, Sha256, Sha2Algorithm, Sha384, Sha512};
 
 fn test_against_sha2_crate<OwnSha, CrateSha>(input: &[u8], parts: NonZeroUsize)
 where
-    OwnSha: Sha2,
-    OwnSha::Output: Debug + Eq,
-    CrateSha: Sha2<Output = OwnSha::Output>,
+    OwnSha: Sha2Algorithm,
+    CrateSha: Digest,
 {
     let own_hash = {
         let mut sha2 = OwnSha::new();
@@ -21,29 +20,23 @@ where
 
         for chunk in input.chunks(chunk_size.max(1)) {
             if chunk_size == 0 {
-                sha2 = sha2.update(&[]);
+                sha2 = sha2.update(&[]).unwrap();
             }
 
-            sha2 = sha2.update(chunk);
+            sha2 = sha2.update(chunk).unwrap();
 
             if chunk_size == 0 {
-                sha2 = sha2.update(&[]);
+                sha2 = sha2.update(&[]).unwrap();
             }
         }
 
         sha2.finalize()
     };
 
-    let expected_hash = {
-        let mut digest = CrateSha::new();
-
-        digest = digest.update(input);
-
-        digest.finalize()
-    };
+    let expected_hash = CrateSha::digest(input);
 
     assert!(
-        own_hash == expected_hash,
+        own_hash.as_ref() == expected_hash.as_slice(),
         "input: {input:02X?}\n  own: {own_hash:02X?}\ncrate: {expected_hash:02X?}"
     );
 }
@@ -234,149 +227,3 @@ proptest! {
         test_against_sha2_crate::<Sha256, sha2::Sha256>(&input, parts);
     }
 }
-
-trait Sha2 {
-    type Output;
-
-    fn new() -> Self;
-
-    fn update(self, message: &[u8]) -> Self;
-
-    fn finalize(self) -> Self::Output;
-}
-
-impl Sha2 for Sha224 {
-    type Output = [u8; 28];
-
-    fn new() -> Self {
-        Self::new()
-    }
-
-    fn update(self, message: &[u8]) -> Self {
-        self.update(message).unwrap()
-    }
-
-    fn finalize(self) -> Self::Output {
-        self.finalize()
-    }
-}
-
-impl Sha2 for sha2::Sha224 {
-    type Output = <Sha224 as Sha2>::Output;
-
-    fn new() -> Self {
-        Digest::new()
-    }
-
-    fn update(mut self, message: &[u8]) -> Self {
-        Digest::update(&mut self, message);
-
-        self
-    }
-
-    fn finalize(self) -> Self::Output {
-        Digest::finalize(self).into()
-    }
-}
-
-impl Sha2 for Sha256 {
-    type Output = [u8; 32];
-
-    fn new() -> Self {
-        Self::new()
-    }
-
-    fn update(self, message: &[u8]) -> Self {
-        self.update(message).unwrap()
-    }
-
-    fn finalize(self) -> Self::Output {
-        self.finalize()
-    }
-}
-
-impl Sha2 for sha2::Sha256 {
-    type Output = <Sha256 as Sha2>::Output;
-
-    fn new() -> Self {
-        Digest::new()
-    }
-
-    fn update(mut self, message: &[u8]) -> Self {
-        Digest::update(&mut self, message);
-
-        self
-    }
-
-    fn finalize(self) -> Self::Output {
-        Digest::finalize(self).into()
-    }
-}
-
-impl Sha2 for Sha384 {
-    type Output = [u8; 48];
-
-    fn new() -> Self {
-        Self::new()
-    }
-
-    fn update(self, message: &[u8]) -> Self {
-        self.update(message).unwrap()
-    }
-
-    fn finalize(self) -> Self::Output {
-        self.finalize()
-    }
-}
-
-impl Sha2 for sha2::Sha384 {
-    type Output = <Sha384 as Sha2>::Output;
-
-    fn new() -> Self {
-        Digest::new()
-    }
-
-    fn update(mut self, message: &[u8]) -> Self {
-        Digest::update(&mut self, message);
-
-        self
-    }
-
-    fn finalize(self) -> Self::Output {
-        Digest::finalize(self).into()
-    }
-}
-
-impl Sha2 for Sha512 {
-    type Output = [u8; 64];
-
-    fn new() -> Self {
-        Self::new()
-    }
-
-    fn update(self, message: &[u8]) -> Self {
-        self.update(message).unwrap()
-    }
-
-    fn finalize(self) -> Self::Output {
-        self.finalize()
-    }
-}
-
-impl Sha2 for sha2::Sha512 {
-    type Output = <Sha512 as Sha2>::Output;
-
-    fn new() -> Self {
-        Digest::new()
-    }
-
-    fn update(mut self, message: &[u8]) -> Self {
-        Digest::update(&mut self, message);
-
-        self
-    }
-
-    fn finalize(self) -> Self::Output {
-        Digest::finalize(self).into()
-    }
-}
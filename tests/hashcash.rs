@@ -0,0 +1,192 @@
+use compile_time_sha2::{
+    error::HashcashError,
+    hashcash::{leading_zero_bits, verify, NonceEncoding, NonceSearch, Stamp, MAX_BITS},
+    Sha256,
+};
+
+/// Stamp whose counter is the lowest one giving 16 leading zero bits, found
+/// with Python's `hashlib`.
+const STAMP: &str = "1:16:261019:alice@example.com::0123456789abcdef:1d6dd";
+
+#[test]
+fn test_leading_zero_bits() {
+    assert_eq!(leading_zero_bits(&[]), 0);
+    assert_eq!(leading_zero_bits(&[0x80]), 0);
+    assert_eq!(leading_zero_bits(&[0x01, 0x00]), 7);
+    assert_eq!(leading_zero_bits(&[0x00, 0x00, 0x3F]), 18);
+    assert_eq!(leading_zero_bits(&[0; 32]), MAX_BITS);
+}
+
+#[test]
+fn test_nonce_digests() {
+    for prefix in [&b""[..], b"prefix", &[0xAB; 55], &[0xCD; 64], &[0xEF; 150]] {
+        let search = NonceSearch::new(prefix, 0).unwrap();
+
+        let hex = search.with_encoding(NonceEncoding::LowerHex);
+
+        for nonce in [0, 9, 10, 0xFF, 0x1234_5678, u64::MAX] {
+            assert_eq!(
+                search.digest(nonce),
+                Sha256::digest_parts(&[prefix, &nonce.to_be_bytes()]).unwrap(),
+            );
+            assert_eq!(
+                hex.digest(nonce),
+                Sha256::digest_parts(&[prefix, format!("{nonce:x}").as_bytes()]).unwrap(),
+            );
+            assert!(search.is_valid(nonce));
+        }
+    }
+}
+
+#[test]
+fn test_search() {
+    let search = NonceSearch::new(b"prefix", 12).unwrap();
+
+    // Found with Python's `hashlib`.
+    assert_eq!(search.search(0..u64::MAX), Some(5093));
+    assert_eq!(search.search(5093..5094), Some(5093));
+    assert_eq!(search.search(0..5093), None);
+    assert_eq!(search.bits(), 12);
+
+    let expected = (0..30_000)
+        .filter(|nonce| leading_zero_bits(&search.digest(*nonce)) >= 12)
+        .collect::<Vec<_>>();
+
+    assert_eq!(expected[0], 5093);
+
+    for pair in expected.windows(2) {
+        assert_eq!(search.search(pair[0] + 1..u64::MAX), Some(pair[1]));
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_search_parallel() {
+    use std::num::NonZeroUsize;
+
+    let search = NonceSearch::new(b"prefix", 12).unwrap();
+
+    for threads in [1, 2, 3, 7, 16] {
+        let threads = NonZeroUsize::new(threads).unwrap();
+
+        for start in [0, 1, 5090, 5093, 5094] {
+            assert_eq!(
+                search.search_parallel(start..u64::MAX, threads),
+                search.search(start..u64::MAX),
+            );
+        }
+
+        assert_eq!(search.search_parallel(0..5093, threads), None);
+        assert_eq!(search.search_parallel(5093..5094, threads), Some(5093));
+        assert_eq!(search.search_parallel(7..7, threads), None);
+    }
+}
+
+#[test]
+#[should_panic = "SHA-256 digests have at most 256 bits!"]
+fn test_too_many_bits() {
+    let _ = NonceSearch::new(b"prefix", MAX_BITS + 1);
+}
+
+#[test]
+fn test_verify() {
+    assert_eq!(
+        verify(STAMP, 16),
+        Ok(Stamp {
+            bits: 16,
+            date: "261019",
+            resource: "alice@example.com",
+            extension: "",
+            rand: "0123456789abcdef",
+            counter: "1d6dd",
+        }),
+    );
+    assert!(verify(STAMP, 0).is_ok());
+
+    assert_eq!(
+        verify(STAMP, 17),
+        Err(HashcashError::InsufficientBits {
+            claimed: 16,
+            required: 17,
+        }),
+    );
+
+    // The digest has exactly 16 leading zero bits.
+    assert_eq!(
+        verify(&STAMP.replacen(":16:", ":17:", 1), 16),
+        Err(HashcashError::InsufficientWork),
+    );
+    assert_eq!(
+        verify(&STAMP.replace("1d6dd", "1d6dc"), 16),
+        Err(HashcashError::InsufficientWork),
+    );
+    assert_eq!(
+        verify(&STAMP.replace("alice", "bob"), 16),
+        Err(HashcashError::InsufficientWork),
+    );
+}
+
+#[test]
+fn test_malformed_stamps() {
+    for (stamp, error) in [
+        ("", HashcashError::UnsupportedVersion),
+        ("0:260101:alice:1234", HashcashError::UnsupportedVersion),
+        (
+            "2:16:261019:alice::rand:0",
+            HashcashError::UnsupportedVersion,
+        ),
+        ("1:16:261019:alice::rand", HashcashError::Malformed),
+        ("1:16:261019:alice::rand:0:0", HashcashError::Malformed),
+        ("1:sixteen:261019:alice::rand:0", HashcashError::Malformed),
+        ("1:-16:261019:alice::rand:0", HashcashError::Malformed),
+    ] {
+        assert_eq!(Stamp::parse(stamp), Err(error));
+        assert_eq!(verify(stamp, 0), Err(error));
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_mint() {
+    use std::num::NonZeroUsize;
+
+    use compile_time_sha2::hashcash::{mint, mint_with};
+
+    for threads in [1, 4] {
+        assert_eq!(
+            mint_with(
+                "alice@example.com",
+                16,
+                "261019",
+                "0123456789abcdef",
+                NonZeroUsize::new(threads).unwrap(),
+            ),
+            Ok(STAMP.to_owned()),
+        );
+    }
+
+    let stamp = mint("bob@example.com", 10).unwrap();
+
+    let parsed = verify(&stamp, 10).unwrap();
+
+    assert_eq!(parsed.resource, "bob@example.com");
+    assert_eq!(parsed.extension, "");
+    assert_eq!(parsed.date.len(), 6);
+    assert!(parsed.date.bytes().all(|byte| byte.is_ascii_digit()));
+    assert_eq!(parsed.rand.len(), 16);
+    assert_ne!(
+        verify(&mint("bob@example.com", 0).unwrap(), 0)
+            .unwrap()
+            .rand,
+        parsed.rand,
+    );
+
+    assert_eq!(
+        mint("bob:example.com", 10),
+        Err(HashcashError::InvalidField)
+    );
+    assert_eq!(
+        mint_with("bob", 10, "26:10", "rand", NonZeroUsize::new(1).unwrap()),
+        Err(HashcashError::InvalidField),
+    );
+}
@@ -0,0 +1,113 @@
+use std::{
+    collections::HashMap,
+    hash::{BuildHasher, Hash, Hasher},
+};
+
+use compile_time_sha2::{
+    hasher::{Sha256Hasher, Sha512Hasher},
+    Sha256, Sha512,
+};
+
+#[derive(Hash)]
+enum Event {
+    Started,
+    Moved { x: i32, y: u64 },
+}
+
+#[derive(Hash)]
+struct Record {
+    id: u16,
+    offset: usize,
+    enabled: bool,
+    initial: char,
+    event: Event,
+}
+
+#[test]
+fn test_portable_bytes() {
+    let record = Record {
+        id: 0x0102,
+        offset: 0x0304,
+        enabled: true,
+        initial: 'é',
+        event: Event::Moved { x: -2, y: 7 },
+    };
+
+    let mut hasher = Sha256Hasher::new();
+
+    record.hash(&mut hasher);
+
+    // Integers in little-endian order, `usize` and `isize` as 64 bits, no
+    // matter the target.
+    let expected = [
+        &0x0102_u16.to_le_bytes()[..],
+        &0x0304_u64.to_le_bytes(),
+        &[1],
+        &u32::from('é').to_le_bytes(),
+        &1_i64.to_le_bytes(),
+        &(-2_i32).to_le_bytes(),
+        &7_u64.to_le_bytes(),
+    ]
+    .concat();
+
+    assert_eq!(hasher.finalize(), Sha256::digest(&expected).unwrap());
+
+    let mut hasher = Sha256Hasher::new();
+
+    Event::Started.hash(&mut hasher);
+
+    assert_eq!(
+        hasher.finalize(),
+        Sha256::digest(&0_i64.to_le_bytes()).unwrap(),
+    );
+}
+
+#[test]
+fn test_finish() {
+    let mut hasher = Sha512Hasher::new();
+
+    hasher.write(b"abc");
+
+    assert_eq!(hasher.finish(), 0xDDAF_35A1_9361_7ABA);
+
+    // Finishing does not consume the hasher.
+    hasher.write(b"def");
+
+    assert_eq!(hasher.finalize(), Sha512::digest(b"abcdef").unwrap());
+}
+
+/// Stands in for `BuildHasher::hash_one`, which needs Rust 1.71.
+fn hash_one<T: Hash>(build_hasher: &Sha256Hasher, value: T) -> u64 {
+    let mut hasher = build_hasher.build_hasher();
+
+    value.hash(&mut hasher);
+
+    hasher.finish()
+}
+
+#[test]
+fn test_build_hasher() {
+    let fresh = Sha256Hasher::new();
+
+    assert_eq!(hash_one(&fresh, 1_u8), hash_one(&fresh, 1_u8));
+
+    assert_ne!(hash_one(&fresh, 1_u8), hash_one(&fresh, 2_u8));
+
+    let mut domain = Sha256Hasher::new();
+
+    domain.write(b"domain");
+
+    assert_ne!(hash_one(&domain, 1_u8), hash_one(&fresh, 1_u8));
+
+    let mut prefixed = Sha256Hasher::from_hasher(Sha256::new().update(b"domain").unwrap());
+
+    prefixed.write_u8(1);
+
+    assert_eq!(hash_one(&domain, 1_u8), prefixed.finish());
+
+    let mut map = HashMap::with_hasher(domain);
+
+    map.insert("key", 1);
+
+    assert_eq!(map.get("key"), Some(&1));
+}
@@ -0,0 +1,66 @@
+use compile_time_sha2::{
+    id::{id128, id64, ID_DOMAIN},
+    stable_ids, Sha256,
+};
+
+stable_ids! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Kind: u64 {
+        Ping = "ping",
+        Empty = "",
+        Qualified = "MessageKind::Ping",
+    }
+}
+
+stable_ids! {
+    #[derive(Debug, PartialEq, Eq)]
+    pub(crate) enum WideKind: u128 {
+        Ping = "ping",
+    }
+}
+
+const PING: u64 = Kind::Ping.id();
+
+#[test]
+fn test_known_ids() {
+    assert_eq!(id64(""), 0xBABE_91C2_5E67_CB6F);
+    assert_eq!(id64("ping"), 0x2ECE_2021_C8FC_69DC);
+    assert_eq!(id64("MessageKind::Ping"), 0x7966_22D2_2D31_FBA7);
+
+    assert_eq!(id128("ping"), 0x2ECE_2021_C8FC_69DC_51AE_1F34_F21B_1B54);
+}
+
+#[test]
+fn test_ids_are_truncated_digests() {
+    for name in [
+        "",
+        "ping",
+        "a longer name spanning more than a single chunk of sixty-four bytes",
+    ] {
+        let digest = Sha256::digest_parts(&[ID_DOMAIN, name.as_bytes()]).unwrap();
+
+        assert_eq!(id64(name).to_be_bytes(), digest[..8]);
+        assert_eq!(id128(name).to_be_bytes(), digest[..16]);
+    }
+}
+
+#[test]
+fn test_stable_ids() {
+    assert_eq!(PING, id64("ping"));
+
+    assert_eq!(Kind::VARIANTS, [Kind::Ping, Kind::Empty, Kind::Qualified]);
+    assert_eq!(Kind::NAMES, ["ping", "", "MessageKind::Ping"]);
+
+    for (index, variant) in Kind::VARIANTS.iter().enumerate() {
+        assert_eq!(variant.name(), Kind::NAMES[index]);
+        assert_eq!(variant.id(), Kind::IDS[index]);
+        assert_eq!(variant.id(), id64(variant.name()));
+        assert_eq!(Kind::from_id(variant.id()), Some(*variant));
+    }
+
+    assert_eq!(Kind::from_id(id64("pong")), None);
+
+    assert_eq!(WideKind::Ping.id(), id128("ping"));
+    assert_eq!(WideKind::from_id(id128("ping")), Some(WideKind::Ping));
+    assert_eq!(WideKind::from_id(u128::from(id64("ping"))), None);
+}
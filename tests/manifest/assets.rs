@@ -0,0 +1,30 @@
+// @generated by compile_time_sha2::build. Do not edit.
+
+pub const ALGORITHM: &str = "SHA-256";
+
+pub struct Entry {
+    pub path: &'static str,
+    pub length: u64,
+    pub digest: [u8; 32],
+}
+
+/// Entries sorted by path.
+pub const ENTRIES: &[Entry] = &[
+    Entry {
+        path: "scripts/app.js",
+        length: 11,
+        digest: *b"\x8e\x60\x9b\xb7\x1c\x20\xb8\x58\xc7\x7f\x0e\x9f\x90\xbb\x13\x19\xdb\x84\x77\xb1\x3f\x9f\x96\x5f\x1a\x1e\x18\x52\x4b\xf5\x08\x81",
+    },
+    Entry {
+        path: "site.css",
+        length: 8,
+        digest: *b"\xa0\x6f\xd7\x50\xde\x73\x74\x98\x3d\xaf\x40\x01\x65\x64\xb1\xfb\x6f\x21\x68\xed\x2c\x57\x42\xcc\xf6\x99\x12\xe8\x57\x48\x03\xc0",
+    },
+];
+
+pub fn get(path: &str) -> Option<&'static Entry> {
+    ENTRIES
+        .binary_search_by(|entry| entry.path.cmp(path))
+        .ok()
+        .map(|index| &ENTRIES[index])
+}
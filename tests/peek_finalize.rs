@@ -0,0 +1,115 @@
+use compile_time_sha2::{algorithm::Algorithm, AnySha2, Sha256, Sha2Multi, Sha384, Sha512};
+
+const RECORDS: [&[u8]; 5] = [
+    b"2024-05-01T10:00:00Z service started\n",
+    b"",
+    b"2024-05-01T10:00:01Z accepted connection from 192.0.2.1\n",
+    &[b'x'; 200],
+    b"2024-05-01T10:00:02Z service stopped\n",
+];
+
+#[test]
+fn test_running_digest() {
+    let mut sha = Sha256::new();
+
+    let mut log = Vec::new();
+
+    for record in RECORDS {
+        sha = sha.update(record).unwrap();
+
+        log.extend_from_slice(record);
+
+        assert_eq!(sha.peek_finalize(), Sha256::digest(&log).unwrap());
+    }
+
+    let never_peeked = RECORDS
+        .into_iter()
+        .try_fold(Sha256::new(), Sha256::update)
+        .unwrap();
+
+    assert_eq!(sha.finalize(), never_peeked.finalize());
+}
+
+#[test]
+fn test_fork() {
+    let common = Sha512::new().update(b"common prefix, ").unwrap();
+
+    let (left, right) = (common, common);
+
+    assert_eq!(
+        left.update(b"left").unwrap().finalize(),
+        Sha512::digest(b"common prefix, left").unwrap(),
+    );
+
+    assert_eq!(
+        right.update(b"right").unwrap().finalize(),
+        Sha512::digest(b"common prefix, right").unwrap(),
+    );
+}
+
+#[test]
+fn test_peek_dynamic() {
+    let mut any = AnySha2::new(Algorithm::Sha384);
+
+    let mut multi = Sha2Multi::new(&Algorithm::ALL);
+
+    let mut log = Vec::new();
+
+    for record in RECORDS {
+        any = any.update(record).unwrap();
+
+        multi = multi.update(record).unwrap();
+
+        log.extend_from_slice(record);
+
+        assert_eq!(
+            any.peek_finalize(),
+            AnySha2::digest(Algorithm::Sha384, &log).unwrap()
+        );
+
+        assert_eq!(
+            multi.peek_finalize(),
+            Sha2Multi::digest(&Algorithm::ALL, &log).unwrap(),
+        );
+    }
+
+    assert_eq!(
+        any.finalize(),
+        AnySha2::digest(Algorithm::Sha384, &log).unwrap()
+    );
+
+    assert_eq!(
+        multi.finalize(),
+        Sha2Multi::digest(&Algorithm::ALL, &log).unwrap()
+    );
+}
+
+#[test]
+fn test_const() {
+    const PEEKED: [u8; 48] = match Sha384::new().update(b"abc") {
+        Ok(sha) => sha.peek_finalize(),
+        Err(_) => panic!("message too long"),
+    };
+
+    assert_eq!(PEEKED, Sha384::digest(b"abc").unwrap());
+}
+
+#[test]
+fn test_debug() {
+    let sha = Sha256::new().update(b"secret").unwrap();
+
+    assert_eq!(format!("{sha:?}"), "Sha256 { message_length: 6, .. }");
+
+    assert_eq!(
+        format!("{:?}", AnySha2::from(sha)),
+        "Sha256(Sha256 { message_length: 6, .. })",
+    );
+
+    assert_eq!(
+        format!(
+            "{:?}",
+            Sha2Multi::new(&[Algorithm::Sha512, Algorithm::Sha224])
+        ),
+        "Sha2Multi { algorithms: [Sha224, Sha512], .. }",
+    );
+}
@@ -0,0 +1,167 @@
+use compile_time_sha2::{
+    algorithm::Algorithm,
+    error::{ParseError, PrefixError},
+    prefix::{unique_prefix_length, DigestPrefix, MAX_PREFIX_DIGITS},
+    AnySha2, Sha256,
+};
+
+const ABC_SHA256: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+const PREFIX: DigestPrefix = match DigestPrefix::parse("BA7816b") {
+    Ok(prefix) => prefix,
+    Err(_) => panic!("invalid prefix"),
+};
+
+#[test]
+fn test_parse_and_display() {
+    assert_eq!(PREFIX.len(), 7);
+    assert_eq!(PREFIX.to_string(), "ba7816b");
+    assert_eq!(format!("{PREFIX:X}"), "BA7816B");
+
+    for length in 0..=ABC_SHA256.len() {
+        let prefix = ABC_SHA256[..length].parse::<DigestPrefix>().unwrap();
+
+        assert_eq!(prefix.len(), length);
+        assert_eq!(prefix.is_empty(), length == 0);
+        assert_eq!(prefix.to_string(), ABC_SHA256[..length]);
+
+        let digest = Sha256::digest(b"abc").unwrap();
+
+        assert_eq!(DigestPrefix::from_digest(&digest, length), prefix);
+    }
+
+    assert_eq!(
+        "ba7g".parse::<DigestPrefix>(),
+        Err(ParseError::InvalidCharacter { position: 3 }),
+    );
+
+    assert_eq!(
+        "0".repeat(MAX_PREFIX_DIGITS + 1).parse::<DigestPrefix>(),
+        Err(ParseError::InvalidLength {
+            expected: MAX_PREFIX_DIGITS,
+            actual: MAX_PREFIX_DIGITS + 1,
+        }),
+    );
+}
+
+#[test]
+fn test_matches() {
+    let digest = Sha256::digest(b"abc").unwrap();
+
+    assert!(PREFIX.matches(&digest));
+    assert!(DigestPrefix::parse("").unwrap().matches(&digest));
+    assert!(DigestPrefix::parse("b").unwrap().matches(&digest));
+    assert!(DigestPrefix::parse(ABC_SHA256).unwrap().matches(&digest));
+
+    assert!(!DigestPrefix::parse("ba7816c").unwrap().matches(&digest));
+    assert!(!DigestPrefix::parse("a").unwrap().matches(&digest));
+    assert!(!DigestPrefix::parse(&format!("{ABC_SHA256}0"))
+        .unwrap()
+        .matches(&digest));
+
+    let any = AnySha2::new(Algorithm::Sha256)
+        .update(b"abc")
+        .unwrap()
+        .finalize();
+
+    assert!(PREFIX.matches(any.as_bytes()));
+}
+
+#[test]
+fn test_resolve() {
+    let digests = [
+        [0x12, 0x34, 0x56],
+        [0x12, 0x35, 0x00],
+        [0x12, 0x35, 0xFF],
+        [0xAB, 0x00, 0x00],
+    ];
+
+    let resolve = |prefix: &str| DigestPrefix::parse(prefix).unwrap().resolve(&digests);
+
+    assert_eq!(resolve("1234"), Ok(&digests[0]));
+    assert_eq!(resolve("12350"), Ok(&digests[1]));
+    assert_eq!(resolve("1235f"), Ok(&digests[2]));
+    assert_eq!(resolve("a"), Ok(&digests[3]));
+
+    assert_eq!(
+        resolve("1235"),
+        Err(PrefixError::Ambiguous { candidates: 2 })
+    );
+    assert_eq!(resolve("1"), Err(PrefixError::Ambiguous { candidates: 3 }));
+    assert_eq!(resolve(""), Err(PrefixError::Ambiguous { candidates: 4 }));
+
+    assert_eq!(resolve("0"), Err(PrefixError::NotFound));
+    assert_eq!(resolve("1236"), Err(PrefixError::NotFound));
+    assert_eq!(resolve("ac"), Err(PrefixError::NotFound));
+    assert_eq!(resolve("ab00000"), Err(PrefixError::NotFound));
+}
+
+#[test]
+fn test_unique_prefix_length() {
+    let digests = [
+        [0x12, 0x34, 0x56],
+        [0x12, 0x35, 0x00],
+        [0x12, 0x35, 0xFF],
+        [0xAB, 0x00, 0x00],
+    ];
+
+    // `12350` and `1235f` share four digits.
+    assert_eq!(unique_prefix_length(&digests, 1), Ok(5));
+    assert_eq!(unique_prefix_length(&digests, 6), Ok(6));
+    assert_eq!(unique_prefix_length(&digests[..2], 1), Ok(4));
+    assert_eq!(unique_prefix_length(&digests[3..], 7), Ok(7));
+    assert_eq!(unique_prefix_length::<[u8; 3]>(&[], 7), Ok(7));
+
+    for digits in 5..=6 {
+        for digest in &digests {
+            let prefix = DigestPrefix::from_digest(digest, digits);
+
+            assert_eq!(prefix.resolve(&digests), Ok(digest));
+        }
+    }
+
+    let repeated = [digests[0], digests[1], digests[1], digests[1]];
+
+    assert_eq!(
+        unique_prefix_length(&repeated, 7),
+        Err(PrefixError::Ambiguous { candidates: 3 }),
+    );
+
+    let nested: [&[u8]; 2] = [&[0x12, 0x34], &[0x12, 0x34, 0x56]];
+
+    assert_eq!(
+        unique_prefix_length(&nested, 1),
+        Err(PrefixError::Ambiguous { candidates: 2 }),
+    );
+}
+
+#[test]
+fn test_many_digests() {
+    let mut digests = (0_u32..2000)
+        .map(|index| Sha256::digest(&index.to_le_bytes()).unwrap())
+        .collect::<Vec<_>>();
+
+    digests.sort_unstable();
+
+    let digits = unique_prefix_length(&digests, 4).unwrap();
+
+    assert!(digits > 4);
+
+    for digest in &digests {
+        assert_eq!(
+            DigestPrefix::from_digest(digest, digits).resolve(&digests),
+            Ok(digest),
+        );
+    }
+
+    let shorter = digests
+        .iter()
+        .filter(|digest| {
+            DigestPrefix::from_digest(*digest, digits - 1)
+                .resolve(&digests)
+                .is_err()
+        })
+        .count();
+
+    assert_ne!(shorter, 0);
+}
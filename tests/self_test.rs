@@ -0,0 +1,15 @@
+use compile_time_sha2::{algorithm::Algorithm, self_test};
+
+#[test]
+fn test_self_test_passes() {
+    let report = self_test();
+
+    assert!(report.is_passed(), "{report:?}");
+
+    assert_eq!(report.failures().count(), 0);
+
+    assert_eq!(
+        report.outcomes().map(|outcome| outcome.algorithm()),
+        Algorithm::ALL,
+    );
+}
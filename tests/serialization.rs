@@ -0,0 +1,125 @@
+#![cfg(feature = "serde")]
+
+use compile_time_sha2::{
+    algorithm::Algorithm, state::ExportedState, AnyDigest, AnySha2, Sha256, Sha384,
+};
+use serde::{Deserialize, Serialize};
+use serde_test::{assert_de_tokens_error, assert_tokens, Configure, Token};
+
+const ABC_SHA256: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+static DIGEST: AnyDigest = match AnySha2::digest(Algorithm::Sha256, b"abc") {
+    Ok(digest) => digest,
+    Err(_) => panic!("message too long"),
+};
+
+static EXPORTED: ExportedState = match Sha256::new().update(b"upload in progress") {
+    Ok(sha) => sha.export_state(),
+    Err(_) => panic!("message too long"),
+};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Upload {
+    #[serde(with = "compile_time_sha2::serialization::digest")]
+    sha256: [u8; 32],
+    #[serde(with = "compile_time_sha2::serialization::digest")]
+    sha384: [u8; 48],
+}
+
+#[test]
+fn test_array_digests() {
+    let upload = Upload {
+        sha256: Sha256::digest(b"abc").unwrap(),
+        sha384: Sha384::digest(b"abc").unwrap(),
+    };
+
+    let json = serde_json::to_string(&upload).unwrap();
+
+    assert_eq!(
+        json,
+        format!(
+            r#"{{"sha256":"{ABC_SHA256}","sha384":"{}"}}"#,
+            "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed\
+             8086072ba1e7cc2358baeca134c825a7",
+        ),
+    );
+
+    assert_eq!(serde_json::from_str::<Upload>(&json).unwrap(), upload);
+
+    let uppercase = json.replace(ABC_SHA256, &ABC_SHA256.to_uppercase());
+
+    assert_eq!(serde_json::from_str::<Upload>(&uppercase).unwrap(), upload);
+
+    for (digest, error) in [
+        (
+            &ABC_SHA256[2..],
+            "invalid length 31, expected a digest of 32 bytes",
+        ),
+        (
+            &ABC_SHA256[1..],
+            "invalid length 63, expected hexadecimal digits or bytes",
+        ),
+        (
+            &format!("{ABC_SHA256}00"),
+            "invalid length 33, expected a digest of 32 bytes",
+        ),
+    ] {
+        let json = json.replace(ABC_SHA256, digest);
+
+        let message = serde_json::from_str::<Upload>(&json)
+            .unwrap_err()
+            .to_string();
+
+        assert!(message.starts_with(error), "{message}");
+    }
+}
+
+#[test]
+fn test_any_digest() {
+    assert_tokens(&DIGEST.readable(), &[Token::Str(ABC_SHA256)]);
+    assert_tokens(&DIGEST.compact(), &[Token::Bytes(DIGEST.as_bytes())]);
+
+    assert_de_tokens_error::<serde_test::Readable<AnyDigest>>(
+        &[Token::Str("00ff")],
+        "invalid length 2, expected a digest of 28, 32, 48 or 64 bytes",
+    );
+
+    assert_de_tokens_error::<serde_test::Readable<AnyDigest>>(
+        &[Token::Str(Box::leak(
+            ABC_SHA256.replace('b', "x").into_boxed_str(),
+        ))],
+        "Invalid character at position 0!",
+    );
+
+    assert_de_tokens_error::<serde_test::Compact<AnyDigest>>(
+        &[Token::Bytes(&[0; 33])],
+        "invalid length 33, expected a digest of 28, 32, 48 or 64 bytes",
+    );
+}
+
+#[test]
+fn test_exported_state() {
+    assert_tokens(&EXPORTED.compact(), &[Token::Bytes(EXPORTED.as_bytes())]);
+
+    let json = serde_json::to_string(&EXPORTED).unwrap();
+
+    assert_eq!(json.len(), 2 * EXPORTED.as_bytes().len() + 2);
+
+    let imported = serde_json::from_str::<ExportedState>(&json).unwrap();
+
+    assert_eq!(
+        Sha256::import_state(&imported)
+            .unwrap()
+            .update(b", done")
+            .unwrap()
+            .finalize(),
+        Sha256::digest(b"upload in progress, done").unwrap(),
+    );
+
+    let truncated = &EXPORTED.as_bytes()[..EXPORTED.as_bytes().len() - 1];
+
+    assert_de_tokens_error::<serde_test::Compact<ExportedState>>(
+        &[Token::Bytes(truncated)],
+        "Invalid exported state length! Expected 67, got 66.",
+    );
+}
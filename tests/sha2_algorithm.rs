@@ -0,0 +1,83 @@
+use compile_time_sha2::{algorithm::Algorithm, Sha224, Sha256, Sha2Algorithm, Sha384, Sha512};
+
+fn constants<H: Sha2Algorithm>() -> (Algorithm, &'static str, usize, usize, u128, &'static str) {
+    (
+        H::ALGORITHM,
+        H::NAME,
+        H::BLOCK_SIZE,
+        H::OUTPUT_SIZE,
+        H::MAX_MESSAGE_BITS,
+        H::OID,
+    )
+}
+
+#[test]
+fn test_constants() {
+    assert_eq!(
+        constants::<Sha224>(),
+        (
+            Algorithm::Sha224,
+            "SHA-224",
+            64,
+            28,
+            u128::from(u64::MAX) - 7,
+            "2.16.840.1.101.3.4.2.4"
+        ),
+    );
+
+    assert_eq!(
+        constants::<Sha256>(),
+        (
+            Algorithm::Sha256,
+            "SHA-256",
+            64,
+            32,
+            u128::from(u64::MAX) - 7,
+            "2.16.840.1.101.3.4.2.1"
+        ),
+    );
+
+    assert_eq!(
+        constants::<Sha384>(),
+        (
+            Algorithm::Sha384,
+            "SHA-384",
+            128,
+            48,
+            u128::MAX - 7,
+            "2.16.840.1.101.3.4.2.2"
+        ),
+    );
+
+    assert_eq!(
+        constants::<Sha512>(),
+        (
+            Algorithm::Sha512,
+            "SHA-512",
+            128,
+            64,
+            u128::MAX - 7,
+            "2.16.840.1.101.3.4.2.3"
+        ),
+    );
+
+    assert_eq!(
+        Sha256::OID_DER,
+        [0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01],
+    );
+}
+
+#[test]
+fn test_generic_digest() {
+    fn digest<H: Sha2Algorithm>(message: &[u8]) -> Vec<u8> {
+        <H as Sha2Algorithm>::digest(message)
+            .unwrap()
+            .as_ref()
+            .to_vec()
+    }
+
+    assert_eq!(digest::<Sha224>(b"abc"), Sha224::digest(b"abc").unwrap());
+    assert_eq!(digest::<Sha256>(b"abc"), Sha256::digest(b"abc").unwrap());
+    assert_eq!(digest::<Sha384>(b"abc"), Sha384::digest(b"abc").unwrap());
+    assert_eq!(digest::<Sha512>(b"abc"), Sha512::digest(b"abc").unwrap());
+}
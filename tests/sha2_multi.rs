@@ -0,0 +1,84 @@
+use compile_time_sha2::{algorithm::Algorithm, AnyDigest, AnySha2, Sha256, Sha2Multi};
+
+/// Lengths around the block boundaries of both families, where the shared
+/// buffer is processed or the padding spills into a second block.
+const LENGTHS: [usize; 12] = [0, 1, 55, 56, 63, 64, 65, 111, 112, 127, 128, 1000];
+
+fn message(length: usize) -> Vec<u8> {
+    (0..length)
+        .map(|index| (index * 31 % 251).to_le_bytes()[0])
+        .collect()
+}
+
+fn expected(algorithms: &[Algorithm], message: &[u8]) -> Vec<AnyDigest> {
+    Algorithm::ALL
+        .into_iter()
+        .filter(|algorithm| algorithms.contains(algorithm))
+        .map(|algorithm| AnySha2::digest(algorithm, message).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_every_selection() {
+    for selection in 0..(1 << Algorithm::ALL.len()) {
+        let algorithms = Algorithm::ALL
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| selection & (1 << index) != 0)
+            .map(|(_, algorithm)| algorithm)
+            .collect::<Vec<_>>();
+
+        for length in LENGTHS {
+            let message = message(length);
+
+            let digests = Sha2Multi::digest(&algorithms, &message).unwrap();
+
+            assert_eq!(
+                digests.iter().collect::<Vec<_>>(),
+                expected(&algorithms, &message),
+                "{algorithms:?}, {length}",
+            );
+
+            for algorithm in Algorithm::ALL {
+                assert_eq!(
+                    digests.get(algorithm).is_some(),
+                    algorithms.contains(&algorithm),
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_split_updates() {
+    let message = message(1000);
+
+    for split in [1, 7, 64, 100, 128, 333] {
+        let digests = message
+            .chunks(split)
+            .try_fold(Sha2Multi::new(&Algorithm::ALL), |sha, part| {
+                sha.update(part)
+            })
+            .unwrap()
+            .finalize();
+
+        assert_eq!(
+            digests.iter().collect::<Vec<_>>(),
+            expected(&Algorithm::ALL, &message),
+            "{split}",
+        );
+    }
+}
+
+#[test]
+fn test_const() {
+    const DIGESTS: compile_time_sha2::MultiDigest =
+        match Sha2Multi::digest(&[Algorithm::Sha256, Algorithm::Sha256], b"abc") {
+            Ok(digests) => digests,
+            Err(_) => panic!("message too long"),
+        };
+
+    assert_eq!(DIGESTS.sha256(), Some(Sha256::digest(b"abc").unwrap()));
+
+    assert_eq!(DIGESTS.iter().count(), 1);
+}
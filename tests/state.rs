@@ -0,0 +1,165 @@
+use compile_time_sha2::{
+    algorithm::Algorithm,
+    error::StateImportError,
+    state::{ExportedState, MAX_EXPORTED_STATE_LENGTH},
+    AnySha2, Sha224, Sha256, Sha384, Sha512,
+};
+
+const SHA256_INITIAL_STATE: [u32; 8] = [
+    0x6A09_E667,
+    0xBB67_AE85,
+    0x3C6E_F372,
+    0xA54F_F53A,
+    0x510E_527F,
+    0x9B05_688C,
+    0x1F83_D9AB,
+    0x5BE0_CD19,
+];
+
+fn message() -> Vec<u8> {
+    (0..=u8::MAX).cycle().take(300).collect()
+}
+
+macro_rules! test_resume {
+    ($($test: ident: $sha_type: ident),+ $(,)?) => {
+        $(
+            #[test]
+            fn $test() {
+                let message = message();
+
+                let expected = $sha_type::digest(&message).unwrap();
+
+                for split in 0..=message.len() {
+                    let (head, tail) = message.split_at(split);
+
+                    let exported = $sha_type::new().update(head).unwrap().export_state();
+
+                    assert_eq!(exported.algorithm(), Algorithm::$sha_type);
+                    assert_eq!(exported.message_length(), head.len() as u128);
+
+                    let parsed = ExportedState::from_bytes(exported.as_bytes()).unwrap();
+
+                    assert_eq!(parsed, exported);
+
+                    let resumed = $sha_type::import_state(&parsed).unwrap();
+
+                    assert_eq!(resumed.update(tail).unwrap().finalize(), expected);
+
+                    let resumed = AnySha2::import_state(&parsed);
+
+                    assert_eq!(resumed.export_state(), exported);
+                    assert_eq!(resumed.update(tail).unwrap().finalize().as_bytes(), expected);
+                }
+            }
+        )+
+    };
+}
+
+test_resume![
+    test_resume_sha224: Sha224,
+    test_resume_sha256: Sha256,
+    test_resume_sha384: Sha384,
+    test_resume_sha512: Sha512,
+];
+
+#[test]
+fn test_format() {
+    let exported = Sha256::new().update(b"abc").unwrap().export_state();
+
+    let mut expected = vec![1];
+
+    expected.extend_from_slice(&3_u128.to_be_bytes());
+
+    for word in SHA256_INITIAL_STATE {
+        expected.extend_from_slice(&word.to_be_bytes());
+    }
+
+    expected.extend_from_slice(b"abc");
+
+    assert_eq!(exported.as_bytes(), expected);
+
+    assert_eq!(
+        format!("{exported:?}"),
+        "ExportedState { algorithm: Sha256, message_length: 3, .. }",
+    );
+
+    let longest = Sha512::new().update(&[0; 255]).unwrap().export_state();
+
+    assert_eq!(longest.as_bytes().len(), MAX_EXPORTED_STATE_LENGTH);
+}
+
+#[test]
+fn test_algorithm_mismatch() {
+    let exported = Sha224::new().update(b"abc").unwrap().export_state();
+
+    assert_eq!(
+        Sha256::import_state(&exported).unwrap_err(),
+        StateImportError::AlgorithmMismatch {
+            expected: Algorithm::Sha256,
+            found: Algorithm::Sha224,
+        },
+    );
+}
+
+#[test]
+fn test_invalid_bytes() {
+    let exported = Sha256::new().update(b"abc").unwrap().export_state();
+
+    let bytes = exported.as_bytes();
+
+    assert_eq!(
+        ExportedState::from_bytes(&[]).unwrap_err(),
+        StateImportError::InvalidLength {
+            expected: 49,
+            actual: 0,
+        },
+    );
+
+    assert_eq!(
+        ExportedState::from_bytes(&bytes[..bytes.len() - 1]).unwrap_err(),
+        StateImportError::InvalidLength {
+            expected: 52,
+            actual: 51,
+        },
+    );
+
+    assert_eq!(
+        ExportedState::from_bytes(&[bytes, b"d"].concat()).unwrap_err(),
+        StateImportError::InvalidLength {
+            expected: 52,
+            actual: 53,
+        },
+    );
+
+    assert_eq!(
+        ExportedState::from_bytes(&bytes[..20]).unwrap_err(),
+        StateImportError::InvalidLength {
+            expected: 49,
+            actual: 20,
+        },
+    );
+
+    for tag in [0, 5, u8::MAX] {
+        let mut invalid = bytes.to_vec();
+
+        invalid[0] = tag;
+
+        assert_eq!(
+            ExportedState::from_bytes(&invalid).unwrap_err(),
+            StateImportError::UnknownAlgorithm { tag },
+        );
+    }
+
+    let mut too_long = bytes.to_vec();
+
+    too_long[1..17].copy_from_slice(&((1_u128 << 61) + 3).to_be_bytes());
+
+    assert_eq!(
+        ExportedState::from_bytes(&too_long).unwrap_err(),
+        StateImportError::MessageLengthOutOfRange,
+    );
+
+    too_long[1..17].copy_from_slice(&((1_u128 << 61) - 61).to_be_bytes());
+
+    assert!(ExportedState::from_bytes(&too_long).is_ok());
+}
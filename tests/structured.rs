@@ -0,0 +1,132 @@
+use compile_time_sha2::{
+    structured::{self, encode_char, encode_length, fingerprint, ENCODING_VERSION},
+    Sha256, Sha512,
+};
+use compile_time_sha2_derive::Sha2Hash;
+
+#[derive(Sha2Hash)]
+struct Endpoint<'a> {
+    port: u16,
+    path: &'a str,
+    secure: bool,
+}
+
+#[derive(Sha2Hash)]
+struct Pair(u8, &'static str);
+
+#[derive(Sha2Hash)]
+enum Shape {
+    Empty,
+    Circle { radius: u32 },
+    Line(i8, i8),
+}
+
+#[derive(Sha2Hash)]
+struct Wrapper<T> {
+    inner: Option<T>,
+}
+
+fn encode<T>(value: &T) -> [u8; 32]
+where
+    T: structured::Sha2Hash + ?Sized,
+{
+    fingerprint::<Sha256, T>(value).unwrap()
+}
+
+fn digest(parts: &[&[u8]]) -> [u8; 32] {
+    Sha256::digest_parts(parts).unwrap()
+}
+
+#[test]
+fn test_concatenation_does_not_collide() {
+    assert_ne!(encode(&("ab", "c")), encode(&("a", "bc")));
+    assert_ne!(
+        encode(&[&[1_u8, 2][..], &[3]]),
+        encode(&[&[1_u8][..], &[2, 3]])
+    );
+    assert_ne!(encode(&Some(0_u8)), encode(&None::<u8>));
+    assert_ne!(encode(&Some(None::<u8>)), encode(&None::<Option<u8>>));
+    assert_ne!(encode(&0_u16), encode(&0_u32));
+}
+
+#[test]
+fn test_encoding() {
+    assert_eq!(
+        encode(&(1_u16, -1_i8, 'é', "hi", [true, false], Some(7_usize))),
+        digest(&[
+            &[ENCODING_VERSION],
+            &[0, 1],
+            &[0xFF],
+            &[0, 0, 0, 0xE9],
+            &[0, 0, 0, 0, 0, 0, 0, 2],
+            b"hi",
+            &[1, 0],
+            &[1, 0, 0, 0, 0, 0, 0, 0, 7],
+        ]),
+    );
+
+    assert_eq!(
+        encode(&[0x1234_u16, 0x5678][..]),
+        digest(&[
+            &[ENCODING_VERSION],
+            &encode_length(2),
+            &[0x12, 0x34, 0x56, 0x78]
+        ]),
+    );
+
+    assert_eq!(
+        fingerprint::<Sha512, _>(&'\u{1F600}').unwrap(),
+        Sha512::digest_parts(&[&[ENCODING_VERSION], &encode_char('\u{1F600}')]).unwrap(),
+    );
+}
+
+#[test]
+fn test_derive() {
+    let endpoint = Endpoint {
+        port: 443,
+        path: "/",
+        secure: true,
+    };
+
+    assert_eq!(encode(&endpoint), encode(&(443_u16, "/", true)));
+
+    assert_eq!(encode(&Pair(3, "x")), encode(&(3_u8, "x")));
+
+    assert_eq!(encode(&Shape::Empty), encode(&0_u32));
+    assert_eq!(
+        encode(&Shape::Circle { radius: 5 }),
+        encode(&(1_u32, 5_u32))
+    );
+    assert_eq!(encode(&Shape::Line(-1, 1)), encode(&(2_u32, -1_i8, 1_i8)));
+
+    assert_eq!(encode(&Wrapper { inner: Some("a") }), encode(&Some("a")));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_collections_are_sorted() {
+    use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+    let entries = [(3_u8, "c"), (1, "a"), (2, "b")];
+
+    let forwards = entries.into_iter().collect::<HashMap<_, _>>();
+    let backwards = entries.into_iter().rev().collect::<HashMap<_, _>>();
+    let sorted = entries.into_iter().collect::<BTreeMap<_, _>>();
+
+    assert_eq!(encode(&forwards), encode(&backwards));
+    assert_eq!(encode(&forwards), encode(&sorted));
+    assert_eq!(
+        encode(&sorted),
+        encode(&(3_usize, (1_u8, "a"), (2_u8, "b"), (3_u8, "c"))),
+    );
+
+    let keys = entries.map(|(key, _)| key);
+
+    assert_eq!(
+        encode(&keys.into_iter().collect::<HashSet<_>>()),
+        encode(&keys.into_iter().collect::<BTreeSet<_>>()),
+    );
+
+    assert_eq!(encode(&String::from("abc")), encode("abc"));
+    assert_eq!(encode(&vec![1_u8, 2]), encode(&[1_u8, 2][..]));
+}
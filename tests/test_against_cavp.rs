@@ -0,0 +1,128 @@
+use std::{fs, path::Path};
+
+use compile_time_sha2::{algorithm::Algorithm, encoding::Hex};
+
+use self::cavp::{monte_carlo, ResponseFile, Vectors, MONTE_CARLO_CHECKPOINTS};
+
+mod cavp;
+
+/// Loads every response file under `tests/vectors`, along with the algorithm
+/// named by its file name, e.g. `SHA256ShortMsg.rsp`.
+///
+/// Files for algorithms which are not implemented, e.g. SHA-512/256, are
+/// skipped. Returns [`None`] when no response file is vendored, see
+/// `tests/vectors/README.md`.
+fn response_files() -> Option<Vec<(String, Algorithm, ResponseFile)>> {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/vectors");
+
+    let mut files = fs::read_dir(directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension() == Some("rsp".as_ref()))
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_str()?.to_owned();
+
+            let algorithm = Algorithm::ALL.into_iter().find(|algorithm| {
+                name.strip_prefix(algorithm.tag_name())
+                    .map_or(false, |rest| {
+                        rest.starts_with(|character: char| character.is_alphabetic())
+                    })
+            })?;
+
+            let file = cavp::parse(&fs::read_to_string(&path).unwrap())
+                .unwrap_or_else(|error| panic!("{name}: {error}"));
+
+            assert_eq!(file.output_length, algorithm.output_length(), "{name}");
+
+            Some((name, algorithm, file))
+        })
+        .collect::<Vec<_>>();
+
+    if files.is_empty() {
+        eprintln!("no CAVP response files in tests/vectors, skipping");
+
+        return None;
+    }
+
+    files.sort_unstable_by(|left, right| left.0.cmp(&right.0));
+
+    Some(files)
+}
+
+#[test]
+fn test_against_cavp_messages() {
+    let Some(files) = response_files() else {
+        return;
+    };
+
+    let mut covered = Vec::new();
+
+    for (name, algorithm, file) in files {
+        let Vectors::Messages(vectors) = file.vectors else {
+            continue;
+        };
+
+        // The hashers only accept whole bytes, so vectors of bit-oriented
+        // implementations cannot be run.
+        for vector in vectors
+            .iter()
+            .filter(|vector| vector.length_in_bits % 8 == 0)
+        {
+            let digest = cavp::digest(algorithm, &vector.message);
+
+            assert!(
+                digest == vector.digest,
+                "file: {name}\nlength: {length}\n  actual: {actual}\nexpected: {expected}",
+                length = vector.length_in_bits,
+                actual = Hex(&digest),
+                expected = Hex(&vector.digest),
+            );
+        }
+
+        covered.push(algorithm);
+    }
+
+    for algorithm in Algorithm::ALL {
+        assert!(
+            covered.contains(&algorithm),
+            "no message vectors for {algorithm}"
+        );
+    }
+}
+
+#[test]
+fn test_against_cavp_monte_carlo() {
+    let Some(files) = response_files() else {
+        return;
+    };
+
+    let mut covered = Vec::new();
+
+    for (name, algorithm, file) in files {
+        let Vectors::MonteCarlo(vector) = file.vectors else {
+            continue;
+        };
+
+        assert_eq!(vector.checkpoints.len(), MONTE_CARLO_CHECKPOINTS, "{name}");
+
+        let checkpoints = monte_carlo(algorithm, &vector.seed, vector.checkpoints.len());
+
+        for (count, (actual, expected)) in checkpoints.iter().zip(&vector.checkpoints).enumerate() {
+            assert!(
+                actual == expected,
+                "file: {name}\ncount: {count}\n  actual: {actual}\nexpected: {expected}",
+                actual = Hex(actual),
+                expected = Hex(expected),
+            );
+        }
+
+        covered.push(algorithm);
+    }
+
+    for algorithm in Algorithm::ALL {
+        assert!(
+            covered.contains(&algorithm),
+            "no Monte Carlo vectors for {algorithm}"
+        );
+    }
+}
@@ -40,3 +40,86 @@ fn test_against_precomputed() {
         );
     }
 }
+
+#[test]
+fn test_one_shot_against_precomputed() {
+    for (index, precomputed) in PRECOMPUTED.iter().enumerate() {
+        let (head, tail) = precomputed.input.split_at(precomputed.input.len() / 2);
+
+        let parts: &[&[u8]] = &[head, &[], tail];
+
+        assert_eq!(
+            Sha224::digest(precomputed.input).unwrap(),
+            precomputed.sha224,
+            "algorithm: SHA224\ninput index: {index}",
+        );
+
+        assert_eq!(
+            Sha224::digest_parts(parts).unwrap(),
+            precomputed.sha224,
+            "algorithm: SHA224\ninput index: {index}",
+        );
+
+        assert_eq!(
+            Sha256::digest(precomputed.input).unwrap(),
+            precomputed.sha256,
+            "algorithm: SHA256\ninput index: {index}",
+        );
+
+        assert_eq!(
+            Sha256::digest_parts(parts).unwrap(),
+            precomputed.sha256,
+            "algorithm: SHA256\ninput index: {index}",
+        );
+
+        assert_eq!(
+            Sha384::digest(precomputed.input).unwrap(),
+            precomputed.sha384,
+            "algorithm: SHA384\ninput index: {index}",
+        );
+
+        assert_eq!(
+            Sha384::digest_parts(parts).unwrap(),
+            precomputed.sha384,
+            "algorithm: SHA384\ninput index: {index}",
+        );
+
+        assert_eq!(
+            Sha512::digest(precomputed.input).unwrap(),
+            precomputed.sha512,
+            "algorithm: SHA512\ninput index: {index}",
+        );
+
+        assert_eq!(
+            Sha512::digest_parts(parts).unwrap(),
+            precomputed.sha512,
+            "algorithm: SHA512\ninput index: {index}",
+        );
+    }
+}
+
+#[test]
+fn test_digest_array_in_const_items() {
+    const INPUT: &[u8; 16] = b"0123456789ABCDEF";
+
+    const SHA224: [u8; 28] = Sha224::digest_array(INPUT);
+
+    const SHA256: [u8; 32] = Sha256::digest_array(INPUT);
+
+    const SHA384: [u8; 48] = Sha384::digest_array(INPUT);
+
+    const SHA512: [u8; 64] = Sha512::digest_array(INPUT);
+
+    let precomputed = PRECOMPUTED
+        .iter()
+        .find(|precomputed| precomputed.input == INPUT)
+        .unwrap();
+
+    assert_eq!(SHA224, precomputed.sha224);
+
+    assert_eq!(SHA256, precomputed.sha256);
+
+    assert_eq!(SHA384, precomputed.sha384);
+
+    assert_eq!(SHA512, precomputed.sha512);
+}
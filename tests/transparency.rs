@@ -0,0 +1,463 @@
+use compile_time_sha2::{
+    encoding::{decode_hex, decode_hex_into},
+    error::ProofError,
+    transparency::{
+        leaf_hash, node_hash, root, root_from_leaf_hashes, verify_consistency, verify_inclusion,
+        EMPTY_ROOT,
+    },
+    Sha256,
+};
+
+/// Leaves of the test data of the Go `merkle` package, used by the Certificate
+/// Transparency implementations. Its roots and proofs below were checked
+/// against a Python implementation of RFC 6962 built on `hashlib`.
+const LEAVES: [&str; 8] = [
+    "",
+    "00",
+    "10",
+    "2021",
+    "3031",
+    "40414243",
+    "5051525354555657",
+    "606162636465666768696a6b6c6d6e6f",
+];
+
+/// Roots of the trees of the first `1..=8` leaves.
+const ROOTS: [&str; 8] = [
+    "6e340b9cffb37a989ca544e6bb780a2c78901d3fb33738768511a30617afa01d",
+    "fac54203e7cc696cf0dfcb42c92a1d9dbaf70ad9e621f4bd8d98662f00e3c125",
+    "aeb6bcfe274b70a14fb067a5e5578264db0fa9b51af5e0ba159158f329e06e77",
+    "d37ee418976dd95753c1c73862b9398fa2a2cf9b4ff0fdfe8b30cd95209614b7",
+    "4e3bbb1f7b478dcfe71fb631631519a3bca12c9aefca1612bfce4c13a86264d4",
+    "76e67dadbcdf1e10e1b74ddc608abd2f98dfb16fbce75277b5232a127f2087ef",
+    "ddb89be403809e325750d3d263cd78929c2942b7942a34b77e122c9594a74c8c",
+    "5dc9da79a70659a9ad559cb701ded9a2ab9d823aad2f4960cfe370eff4604328",
+];
+
+/// Inclusion proofs of the leaf at an index in a tree of a size.
+const INCLUSION_PROOFS: [(u64, u64, &[&str]); 5] = [
+    (0, 1, &[]),
+    (
+        0,
+        8,
+        &[
+            "96a296d224f285c67bee93c30f8a309157f0daa35dc5b87e410b78630a09cfc7",
+            "5f083f0a1a33ca076a95279832580db3e0ef4584bdff1f54c8a360f50de3031e",
+            "6b47aaf29ee3c2af9af889bc1fb9254dabd31177f16232dd6aab035ca39bf6e4",
+        ],
+    ),
+    (
+        5,
+        8,
+        &[
+            "bc1a0643b12e4d2d7c77918f44e0f4f79a838b6cf9ec5b5c283e1f4d88599e6b",
+            "ca854ea128ed050b41b35ffc1b87b8eb2bde461e9e3b5596ece6b9d5975a0ae0",
+            "d37ee418976dd95753c1c73862b9398fa2a2cf9b4ff0fdfe8b30cd95209614b7",
+        ],
+    ),
+    (
+        2,
+        3,
+        &["fac54203e7cc696cf0dfcb42c92a1d9dbaf70ad9e621f4bd8d98662f00e3c125"],
+    ),
+    (
+        1,
+        5,
+        &[
+            "6e340b9cffb37a989ca544e6bb780a2c78901d3fb33738768511a30617afa01d",
+            "5f083f0a1a33ca076a95279832580db3e0ef4584bdff1f54c8a360f50de3031e",
+            "bc1a0643b12e4d2d7c77918f44e0f4f79a838b6cf9ec5b5c283e1f4d88599e6b",
+        ],
+    ),
+];
+
+/// Consistency proofs between trees of an old and a new size.
+const CONSISTENCY_PROOFS: [(u64, u64, &[&str]); 6] = [
+    (1, 1, &[]),
+    (
+        1,
+        8,
+        &[
+            "96a296d224f285c67bee93c30f8a309157f0daa35dc5b87e410b78630a09cfc7",
+            "5f083f0a1a33ca076a95279832580db3e0ef4584bdff1f54c8a360f50de3031e",
+            "6b47aaf29ee3c2af9af889bc1fb9254dabd31177f16232dd6aab035ca39bf6e4",
+        ],
+    ),
+    (
+        6,
+        8,
+        &[
+            "0ebc5d3437fbe2db158b9f126a1d118e308181031d0a949f8dededebc558ef6a",
+            "ca854ea128ed050b41b35ffc1b87b8eb2bde461e9e3b5596ece6b9d5975a0ae0",
+            "d37ee418976dd95753c1c73862b9398fa2a2cf9b4ff0fdfe8b30cd95209614b7",
+        ],
+    ),
+    (
+        2,
+        5,
+        &[
+            "5f083f0a1a33ca076a95279832580db3e0ef4584bdff1f54c8a360f50de3031e",
+            "bc1a0643b12e4d2d7c77918f44e0f4f79a838b6cf9ec5b5c283e1f4d88599e6b",
+        ],
+    ),
+    (
+        3,
+        7,
+        &[
+            "0298d122906dcfc10892cb53a73992fc5b9f493ea4c9badb27b791b4127a7fe7",
+            "07506a85fd9dd2f120eb694f86011e5bb4662e5c415a62917033d4a9624487e7",
+            "fac54203e7cc696cf0dfcb42c92a1d9dbaf70ad9e621f4bd8d98662f00e3c125",
+            "837dbb152e9b079010717e84e865da4ebc0fa198a806d59d31bf15accef22d0e",
+        ],
+    ),
+    (
+        4,
+        7,
+        &["837dbb152e9b079010717e84e865da4ebc0fa198a806d59d31bf15accef22d0e"],
+    ),
+];
+
+fn leaves() -> Vec<Vec<u8>> {
+    LEAVES
+        .iter()
+        .map(|leaf| {
+            let mut bytes = vec![0; leaf.len() / 2];
+
+            decode_hex_into(leaf, &mut bytes).unwrap();
+
+            bytes
+        })
+        .collect()
+}
+
+fn hash(hex: &str) -> [u8; 32] {
+    decode_hex(hex).unwrap()
+}
+
+fn proof(hashes: &[&str]) -> Vec<[u8; 32]> {
+    hashes.iter().map(|hex| hash(hex)).collect()
+}
+
+fn root_of(size: u64) -> [u8; 32] {
+    match size {
+        0 => EMPTY_ROOT,
+        size => hash(ROOTS[usize::try_from(size).unwrap() - 1]),
+    }
+}
+
+#[test]
+fn test_hashes() {
+    assert_eq!(EMPTY_ROOT, Sha256::digest(b"").unwrap());
+    assert_eq!(
+        leaf_hash(b"leaf"),
+        Sha256::digest_parts(&[&[0x00], b"leaf"])
+    );
+    assert_eq!(
+        node_hash(&[0xAA; 32], &[0xBB; 32]),
+        Sha256::digest_parts(&[&[0x01], &[0xAA; 32], &[0xBB; 32]]).unwrap(),
+    );
+}
+
+#[test]
+fn test_roots() {
+    let leaves = leaves();
+
+    let leaf_hashes = leaves
+        .iter()
+        .map(|leaf| leaf_hash(leaf).unwrap())
+        .collect::<Vec<_>>();
+
+    for size in 0..=leaves.len() {
+        let expected = root_of(size as u64);
+
+        assert_eq!(root(&leaves[..size]), Ok(expected));
+        assert_eq!(root_from_leaf_hashes(&leaf_hashes[..size]), expected);
+    }
+}
+
+#[test]
+fn test_inclusion_proofs() {
+    let leaves = leaves();
+
+    for (index, size, hashes) in INCLUSION_PROOFS {
+        let leaf = leaf_hash(&leaves[usize::try_from(index).unwrap()]).unwrap();
+
+        let proof = proof(hashes);
+
+        let root = root_of(size);
+
+        assert_eq!(verify_inclusion(&leaf, index, size, &proof, &root), Ok(()));
+
+        assert_eq!(
+            verify_inclusion(&leaf, index, size, &proof, &[0; 32]),
+            Err(ProofError::RootMismatch),
+        );
+        assert_eq!(
+            verify_inclusion(&[0; 32], index, size, &proof, &root),
+            Err(ProofError::RootMismatch),
+        );
+
+        for position in 0..proof.len() {
+            let mut tampered = proof.clone();
+
+            tampered[position][0] ^= 1;
+
+            assert_eq!(
+                verify_inclusion(&leaf, index, size, &tampered, &root),
+                Err(ProofError::RootMismatch),
+            );
+        }
+
+        let extended = [&proof[..], &[[0; 32]]].concat();
+
+        assert_eq!(
+            verify_inclusion(&leaf, index, size, &extended, &root),
+            Err(ProofError::InvalidProofLength),
+        );
+
+        if !proof.is_empty() {
+            assert_eq!(
+                verify_inclusion(&leaf, index, size, &proof[1..], &root),
+                Err(ProofError::InvalidProofLength),
+            );
+        }
+
+        assert_eq!(
+            verify_inclusion(&leaf, size, size, &proof, &root),
+            Err(ProofError::IndexOutOfRange { index: size, size }),
+        );
+    }
+}
+
+#[test]
+fn test_consistency_proofs() {
+    for (old_size, new_size, hashes) in CONSISTENCY_PROOFS {
+        let proof = proof(hashes);
+
+        let (old_root, new_root) = (root_of(old_size), root_of(new_size));
+
+        assert_eq!(
+            verify_consistency(old_size, new_size, &old_root, &new_root, &proof),
+            Ok(()),
+        );
+
+        if old_size != new_size {
+            assert_eq!(
+                verify_consistency(old_size, new_size, &new_root, &new_root, &proof),
+                Err(ProofError::RootMismatch),
+            );
+        }
+
+        assert_eq!(
+            verify_consistency(old_size, new_size, &old_root, &[0; 32], &proof),
+            Err(ProofError::RootMismatch),
+        );
+
+        for position in 0..proof.len() {
+            let mut tampered = proof.clone();
+
+            tampered[position][31] ^= 0x80;
+
+            assert_eq!(
+                verify_consistency(old_size, new_size, &old_root, &new_root, &tampered),
+                Err(ProofError::RootMismatch),
+            );
+        }
+
+        let extended = [&proof[..], &[[0; 32]]].concat();
+
+        assert_eq!(
+            verify_consistency(old_size, new_size, &old_root, &new_root, &extended),
+            Err(ProofError::InvalidProofLength),
+        );
+
+        assert_eq!(
+            verify_consistency(new_size + 1, new_size, &old_root, &new_root, &proof),
+            Err(ProofError::SizeOutOfRange {
+                size: new_size + 1,
+                maximum: new_size,
+            }),
+        );
+    }
+
+    // The empty tree is a prefix of every tree, without proof.
+    assert_eq!(
+        verify_consistency(0, 5, &EMPTY_ROOT, &root_of(5), &[]),
+        Ok(())
+    );
+    assert_eq!(
+        verify_consistency(0, 5, &root_of(1), &root_of(5), &[]),
+        Err(ProofError::RootMismatch),
+    );
+    assert_eq!(
+        verify_consistency(0, 5, &EMPTY_ROOT, &root_of(5), &[[0; 32]]),
+        Err(ProofError::InvalidProofLength),
+    );
+    assert_eq!(
+        verify_consistency(2, 5, &root_of(2), &root_of(5), &[]),
+        Err(ProofError::InvalidProofLength),
+    );
+}
+
+#[cfg(feature = "std")]
+mod tree {
+    use compile_time_sha2::{
+        error::ProofError,
+        transparency::{leaf_hash, verify_consistency, verify_inclusion, MerkleTree, EMPTY_ROOT},
+    };
+
+    use super::{hash, leaves, proof, root_of, CONSISTENCY_PROOFS, INCLUSION_PROOFS};
+
+    fn tree() -> MerkleTree {
+        let mut tree = MerkleTree::new();
+
+        for (index, leaf) in leaves().iter().enumerate() {
+            assert_eq!(tree.push(leaf), Ok(index as u64));
+        }
+
+        tree
+    }
+
+    #[test]
+    fn test_vectors() {
+        let tree = tree();
+
+        assert_eq!(tree.len(), 8);
+        assert_eq!(tree.root(), root_of(8));
+
+        for size in 0..=8 {
+            assert_eq!(tree.root_at(size), Ok(root_of(size)));
+        }
+
+        for (index, size, hashes) in INCLUSION_PROOFS {
+            assert_eq!(tree.inclusion_proof(index, size), Ok(proof(hashes)));
+        }
+
+        for (old_size, new_size, hashes) in CONSISTENCY_PROOFS {
+            assert_eq!(
+                tree.consistency_proof(old_size, new_size),
+                Ok(proof(hashes))
+            );
+        }
+
+        assert_eq!(
+            tree.leaf_hash(1),
+            Some(hash(
+                "96a296d224f285c67bee93c30f8a309157f0daa35dc5b87e410b78630a09cfc7"
+            )),
+        );
+        assert_eq!(tree.leaf_hash(8), None);
+    }
+
+    #[test]
+    fn test_empty_tree() {
+        let tree = MerkleTree::new();
+
+        assert!(tree.is_empty());
+        assert_eq!(tree.root(), EMPTY_ROOT);
+        assert_eq!(tree.root_at(0), Ok(EMPTY_ROOT));
+        assert_eq!(tree.consistency_proof(0, 0), Ok(Vec::new()));
+        assert_eq!(
+            tree.inclusion_proof(0, 0),
+            Err(ProofError::IndexOutOfRange { index: 0, size: 0 }),
+        );
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let tree = tree();
+
+        assert_eq!(
+            tree.root_at(9),
+            Err(ProofError::SizeOutOfRange {
+                size: 9,
+                maximum: 8
+            }),
+        );
+        assert_eq!(
+            tree.inclusion_proof(0, 9),
+            Err(ProofError::SizeOutOfRange {
+                size: 9,
+                maximum: 8
+            }),
+        );
+        assert_eq!(
+            tree.inclusion_proof(5, 5),
+            Err(ProofError::IndexOutOfRange { index: 5, size: 5 }),
+        );
+        assert_eq!(
+            tree.consistency_proof(6, 5),
+            Err(ProofError::SizeOutOfRange {
+                size: 6,
+                maximum: 5
+            }),
+        );
+        assert_eq!(
+            tree.consistency_proof(1, u64::MAX),
+            Err(ProofError::SizeOutOfRange {
+                size: u64::MAX,
+                maximum: 8,
+            }),
+        );
+    }
+
+    #[test]
+    fn test_every_proof() {
+        let mut tree = MerkleTree::new();
+
+        let mut leaf_hashes = Vec::new();
+
+        for leaf in 0_u32..70 {
+            leaf_hashes.push(leaf_hash(&leaf.to_le_bytes()).unwrap());
+
+            tree.push_leaf_hash(*leaf_hashes.last().unwrap());
+        }
+
+        let roots = (0..=tree.len())
+            .map(|size| tree.root_at(size).unwrap())
+            .collect::<Vec<_>>();
+
+        for size in 0..=tree.len() {
+            let root = roots[size as usize];
+
+            assert_eq!(
+                root,
+                super::root_from_leaf_hashes(&leaf_hashes[..size as usize])
+            );
+
+            for index in 0..size {
+                let proof = tree.inclusion_proof(index, size).unwrap();
+
+                let leaf = leaf_hashes[index as usize];
+
+                assert_eq!(verify_inclusion(&leaf, index, size, &proof, &root), Ok(()));
+
+                if size > 1 {
+                    let other = (index + 1) % size;
+
+                    assert!(verify_inclusion(&leaf, other, size, &proof, &root).is_err());
+                }
+            }
+
+            for old_size in 0..=size {
+                let proof = tree.consistency_proof(old_size, size).unwrap();
+
+                let old_root = roots[old_size as usize];
+
+                assert_eq!(
+                    verify_consistency(old_size, size, &old_root, &root, &proof),
+                    Ok(()),
+                );
+
+                if old_size != 0 && old_size != size {
+                    let wrong_root = roots[old_size as usize - 1];
+
+                    assert_eq!(
+                        verify_consistency(old_size, size, &wrong_root, &root, &proof),
+                        Err(ProofError::RootMismatch),
+                    );
+                }
+            }
+        }
+    }
+}
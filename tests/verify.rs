@@ -0,0 +1,113 @@
+use compile_time_sha2::{
+    encoding::{decode_base64, decode_digest, decode_hex, Hex},
+    error::ParseError,
+    verify_sha224, verify_sha256, verify_sha384, verify_sha512,
+};
+
+const INPUT: &[u8] = b"Hello, world!";
+
+#[test]
+fn test_verify_macros_yield_bytes() {
+    const SHA224_HEX: &[u8] = verify_sha224!(
+        INPUT,
+        "8552d8b7a7dc5476cb9e25dee69a8091290764b7f2a64fe6e78e9568",
+    );
+
+    const SHA224_BASE64: &[u8] = verify_sha224!(INPUT, "hVLYt6fcVHbLniXe5pqAkSkHZLfypk/m546VaA==");
+
+    const SHA256_HEX: &[u8] = verify_sha256!(
+        INPUT,
+        "315F5BDB76D078C43B8AC0064E4A0164612B1FCE77C869345BFC94C75894EDD3",
+    );
+
+    const SHA256_BASE64: &[u8] =
+        verify_sha256!(INPUT, "MV9b23bQeMQ7isAGTkoBZGErH853yGk0W/yUx1iU7dM");
+
+    const SHA384_HEX: &[u8] = verify_sha384!(
+        INPUT,
+        "55bc556b0d2fe0fce582ba5fe07baafff035653638c7ac0d5494c2a64c0bea1cc57331c7c12a45cdbca7f4c34a089eeb",
+    );
+
+    const SHA384_BASE64: &[u8] = verify_sha384!(
+        INPUT,
+        "VbxVaw0v4Pzlgrpf4Huq//A1ZTY4x6wNVJTCpkwL6hzFczHHwSpFzbyn9MNKCJ7r",
+    );
+
+    const SHA512_HEX: &[u8] = verify_sha512!(
+        INPUT,
+        "c1527cd893c124773d811911970c8fe6e857d6df5dc9226bd8a160614c0cd963a4ddea2b94bb7d36021ef9d865d5cea294a82dd49a0bb269f51f6e7a57f79421",
+    );
+
+    const SHA512_BASE64: &[u8] = verify_sha512!(
+        INPUT,
+        "wVJ82JPBJHc9gRkRlwyP5uhX1t9dySJr2KFgYUwM2WOk3eorlLt9NgIe+dhl1c6ilKgt1JoLsmn1H256V/eUIQ==",
+    );
+
+    for bytes in [
+        SHA224_HEX,
+        SHA224_BASE64,
+        SHA256_HEX,
+        SHA256_BASE64,
+        SHA384_HEX,
+        SHA384_BASE64,
+        SHA512_HEX,
+        SHA512_BASE64,
+    ] {
+        assert_eq!(bytes, INPUT);
+    }
+}
+
+#[test]
+fn test_decoding() {
+    assert_eq!(decode_hex::<2>("0aFf"), Ok([0x0A, 0xFF]));
+
+    assert_eq!(
+        decode_hex::<2>("0aF"),
+        Err(ParseError::InvalidLength {
+            expected: 4,
+            actual: 3,
+        }),
+    );
+
+    assert_eq!(
+        decode_hex::<2>("0aFg"),
+        Err(ParseError::InvalidCharacter { position: 3 }),
+    );
+
+    assert_eq!(decode_base64::<1>("/w=="), Ok([0xFF]));
+
+    assert_eq!(decode_base64::<2>("//8"), Ok([0xFF, 0xFF]));
+
+    assert_eq!(decode_base64::<3>("////"), Ok([0xFF; 3]));
+
+    assert_eq!(
+        decode_base64::<1>("/x=="),
+        Err(ParseError::InvalidCharacter { position: 1 }),
+    );
+
+    assert_eq!(
+        decode_base64::<1>("/w="),
+        Err(ParseError::InvalidLength {
+            expected: 4,
+            actual: 3,
+        }),
+    );
+
+    assert_eq!(
+        decode_base64::<2>("/-8="),
+        Err(ParseError::InvalidCharacter { position: 1 }),
+    );
+
+    assert_eq!(decode_digest::<1>("ff"), Ok([0xFF]));
+
+    assert_eq!(decode_digest::<1>("/w=="), Ok([0xFF]));
+}
+
+#[test]
+fn test_hex_display() {
+    let bytes = [0x00, 0x0A, 0xFF];
+
+    assert_eq!(Hex(&bytes).to_string(), "000aff");
+
+    assert_eq!(format!("{:X}", Hex(&bytes)), "000AFF");
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "zeroize")]
+
+use std::{
+    mem::{size_of, MaybeUninit},
+    ptr,
+};
+
+use compile_time_sha2::{algorithm::Algorithm, Sha224, Sha256, Sha384, Sha512};
+use zeroize::{Zeroize, Zeroizing};
+
+/// Returns the memory occupied by the hasher.
+///
+/// The hashers hold no padding, thus every byte is initialized.
+fn memory_of<T>(value: &T) -> &[u8] {
+    unsafe { std::slice::from_raw_parts((value as *const T).cast::<u8>(), size_of::<T>()) }
+}
+
+const SECRET: &[u8] = &[0xA5; 100];
+
+#[test]
+fn test_zeroize() {
+    macro_rules! test_zeroize {
+        ($($sha_type: ident),+) => {
+            $(
+                let mut sha = $sha_type::new().update(SECRET).unwrap();
+
+                assert!(memory_of(&sha).contains(&0xA5));
+
+                sha.zeroize();
+
+                assert!(memory_of(&sha).iter().all(|&byte| byte == 0), stringify!($sha_type));
+            )+
+        };
+    }
+
+    test_zeroize!(Sha224, Sha256, Sha384, Sha512);
+}
+
+#[test]
+fn test_zeroizing_wipes_on_drop() {
+    let mut slot = MaybeUninit::new(Zeroizing::new(Sha256::new().update(SECRET).unwrap()));
+
+    // The slot outlives the hasher dropped in it, so its bytes can still be
+    // read through the pointer, as written by the drop.
+    let sha = slot.as_mut_ptr();
+
+    assert!(memory_of(unsafe { &**sha }).contains(&0xA5));
+
+    unsafe { ptr::drop_in_place(sha) };
+
+    let memory =
+        unsafe { std::slice::from_raw_parts(sha.cast::<u8>(), size_of::<Zeroizing<Sha256>>()) };
+
+    assert!(memory.iter().all(|&byte| byte == 0));
+}
+
+#[test]
+fn test_zeroize_exported_state() {
+    let mut exported = Sha384::new().update(SECRET).unwrap().export_state();
+
+    assert!(exported.as_bytes().contains(&0xA5));
+
+    exported.zeroize();
+
+    assert_eq!(exported.algorithm(), Algorithm::Sha384);
+    assert_eq!(exported.message_length(), 0);
+    assert!(exported.as_bytes()[1..].iter().all(|&byte| byte == 0));
+    assert!(!memory_of(&exported).contains(&0xA5));
+}
+
+#[test]
+fn test_usable_in_const_items() {
+    const DIGEST: [u8; 32] = match Sha256::new().update(b"abc") {
+        Ok(sha) => sha.finalize(),
+        Err(_) => panic!(),
+    };
+
+    assert_eq!(DIGEST, Sha256::digest(b"abc").unwrap());
+}